@@ -0,0 +1,147 @@
+use crate::{
+    boundary::{boundary_parameter, checked_or_random_boundary},
+    mime::write_multipart,
+    MimePart,
+};
+
+/// Simple builder for multipart/alternative test
+///
+/// Parts are representations of the same content, written in insertion order which per
+/// RFC 2046 goes from least to most preferred
+///
+/// # Examples
+///
+/// ```
+/// use actix_multipart_test::MultipartAlternativeBuilder;
+///
+/// let mut multipart_alternative_builder = MultipartAlternativeBuilder::new();
+/// multipart_alternative_builder
+///     .with_plain_text("Hello")
+///     .with_html("<p>Hello</p>");
+/// let (header, body) = multipart_alternative_builder.build();
+/// ```
+#[derive(Default)]
+pub struct MultipartAlternativeBuilder {
+    parts: Vec<MimePart>,
+    boundary: Option<String>,
+}
+
+impl MultipartAlternativeBuilder {
+    /// Create new MultipartAlternativeBuilder
+    pub fn new() -> MultipartAlternativeBuilder {
+        MultipartAlternativeBuilder {
+            parts: vec![],
+            boundary: None,
+        }
+    }
+
+    /// Set a fixed boundary used by every build, e.g. for snapshot or golden tests
+    ///
+    /// boundary is checked against RFC 2046 on build, builds panic when it is invalid
+    ///
+    /// Returns &mut MultipartAlternativeBuilder
+    pub fn with_boundary(
+        &mut self,
+        boundary: impl Into<String>,
+    ) -> &mut MultipartAlternativeBuilder {
+        self.boundary = Some(boundary.into());
+        self
+    }
+
+    /// Add representation
+    ///
+    /// Returns &mut MultipartAlternativeBuilder
+    pub fn with_part(&mut self, part: MimePart) -> &mut MultipartAlternativeBuilder {
+        self.parts.push(part);
+        self
+    }
+
+    /// Add text/plain representation
+    ///
+    /// Returns &mut MultipartAlternativeBuilder
+    pub fn with_plain_text(&mut self, text: impl Into<String>) -> &mut MultipartAlternativeBuilder {
+        self.with_part(MimePart::new("text/plain; charset=utf-8", text.into()))
+    }
+
+    /// Add text/html representation
+    ///
+    /// Returns &mut MultipartAlternativeBuilder
+    pub fn with_html(&mut self, html: impl Into<String>) -> &mut MultipartAlternativeBuilder {
+        self.with_part(MimePart::new("text/html; charset=utf-8", html.into()))
+    }
+
+    /// Build multipart/alternative
+    ///
+    /// Returns ((header_name, header_value), body)
+    ///
+    /// header_name is "Content-Type"
+    ///
+    /// header_value is "multipart/alternative; boundary=..."
+    ///
+    /// body is binary data
+    pub fn build(&self) -> ((String, String), Vec<u8>) {
+        let boundary = checked_or_random_boundary(self.boundary.as_deref());
+        let header = (
+            "Content-Type".to_string(),
+            format!(
+                "multipart/alternative; boundary={}",
+                boundary_parameter(&boundary)
+            ),
+        );
+        (header, write_multipart(&boundary, &self.parts))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MultiPartFormDataParser;
+
+    #[test]
+    fn test_should_build_multipart_alternative_in_order() {
+        let mut multipart_alternative_builder = MultipartAlternativeBuilder::new();
+        multipart_alternative_builder
+            .with_plain_text("Hello")
+            .with_html("<p>Hello</p>")
+            .with_part(MimePart::new("text/markdown", "**Hello**"));
+        let ((_, content_type), body) = multipart_alternative_builder.build();
+
+        assert!(content_type.starts_with("multipart/alternative; boundary="));
+        let parsed = MultiPartFormDataParser::parse(&content_type, &body).unwrap();
+        let content_types: Vec<_> = parsed
+            .parts
+            .iter()
+            .map(|part| part.content_type.as_deref().unwrap())
+            .collect();
+        assert_eq!(
+            content_types,
+            [
+                "text/plain; charset=utf-8",
+                "text/html; charset=utf-8",
+                "text/markdown"
+            ]
+        );
+        assert_eq!(parsed.parts[1].text(), Some("<p>Hello</p>"));
+    }
+
+    #[test]
+    fn test_should_build_multipart_alternative_with_fixed_boundary() {
+        let mut multipart_alternative_builder = MultipartAlternativeBuilder::new();
+        multipart_alternative_builder
+            .with_plain_text("Hello")
+            .with_boundary("alternative");
+        let ((_, content_type), body) = multipart_alternative_builder.build();
+
+        assert_eq!(content_type, "multipart/alternative; boundary=alternative");
+        assert_eq!(body, multipart_alternative_builder.build().1);
+        assert!(body.starts_with(b"--alternative\r\n"));
+    }
+
+    #[test]
+    #[should_panic(expected = "boundary ends with a space")]
+    fn test_should_panic_on_invalid_alternative_boundary() {
+        MultipartAlternativeBuilder::new()
+            .with_boundary("alternative ")
+            .build();
+    }
+}
@@ -0,0 +1,186 @@
+use crate::{ParsedMultiPartFormData, ParsedPart};
+
+impl ParsedMultiPartFormData {
+    /// Assert part with name exists
+    ///
+    /// Returns &ParsedPart to chain part assertions
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use actix_multipart_test::{MultiPartFormDataBuilder, MultiPartFormDataParser};
+    ///
+    /// let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+    /// multipart_form_data_builder.with_file("tests/sample.png", "avatar", "image/png", "sample.png");
+    /// multipart_form_data_builder.with_text("name", "bob");
+    /// let ((_, content_type), body) = multipart_form_data_builder.build();
+    ///
+    /// let parsed = MultiPartFormDataParser::parse(&content_type, &body).unwrap();
+    /// parsed
+    ///     .assert_part_count(2)
+    ///     .assert_field("name", "bob")
+    ///     .assert_part("avatar")
+    ///     .assert_content_type("image/png")
+    ///     .assert_file_name("sample.png");
+    /// ```
+    #[track_caller]
+    pub fn assert_part(&self, name: &str) -> &ParsedPart {
+        match self.part(name) {
+            Some(part) => part,
+            None => panic!(
+                "expected part \"{}\", found parts {:?}",
+                name,
+                self.part_names()
+            ),
+        }
+    }
+
+    /// Assert no part with name exists
+    #[track_caller]
+    pub fn assert_no_part(&self, name: &str) -> &ParsedMultiPartFormData {
+        assert!(
+            self.part(name).is_none(),
+            "expected no part \"{}\", found parts {:?}",
+            name,
+            self.part_names()
+        );
+        self
+    }
+
+    /// Assert text field with name has value
+    #[track_caller]
+    pub fn assert_field(&self, name: &str, value: &str) -> &ParsedMultiPartFormData {
+        self.assert_part(name).assert_text(value);
+        self
+    }
+
+    /// Assert number of parts
+    #[track_caller]
+    pub fn assert_part_count(&self, count: usize) -> &ParsedMultiPartFormData {
+        assert_eq!(
+            self.parts.len(),
+            count,
+            "expected {} parts, found parts {:?}",
+            count,
+            self.part_names()
+        );
+        self
+    }
+
+    fn part_names(&self) -> Vec<&str> {
+        self.parts
+            .iter()
+            .map(|part| part.name.as_deref().unwrap_or(""))
+            .collect()
+    }
+}
+
+impl ParsedPart {
+    /// Assert Content-Type header value
+    #[track_caller]
+    pub fn assert_content_type(&self, content_type: &str) -> &ParsedPart {
+        assert_eq!(
+            self.content_type.as_deref(),
+            Some(content_type),
+            "unexpected content type of part {:?}",
+            self.name
+        );
+        self
+    }
+
+    /// Assert filename parameter of Content-Disposition
+    #[track_caller]
+    pub fn assert_file_name(&self, file_name: &str) -> &ParsedPart {
+        assert_eq!(
+            self.file_name.as_deref(),
+            Some(file_name),
+            "unexpected file name of part {:?}",
+            self.name
+        );
+        self
+    }
+
+    /// Assert header value, name is case insensitive
+    #[track_caller]
+    pub fn assert_header(&self, name: &str, value: &str) -> &ParsedPart {
+        assert_eq!(
+            self.header(name),
+            Some(value),
+            "unexpected header {} of part {:?}",
+            name,
+            self.name
+        );
+        self
+    }
+
+    /// Assert content length in bytes
+    #[track_caller]
+    pub fn assert_len(&self, len: usize) -> &ParsedPart {
+        assert_eq!(
+            self.data.len(),
+            len,
+            "unexpected length of part {:?}",
+            self.name
+        );
+        self
+    }
+
+    /// Assert content bytes
+    #[track_caller]
+    pub fn assert_data(&self, data: impl AsRef<[u8]>) -> &ParsedPart {
+        assert!(
+            self.data == data.as_ref(),
+            "unexpected content of part {:?}",
+            self.name
+        );
+        self
+    }
+
+    /// Assert content text
+    #[track_caller]
+    pub fn assert_text(&self, text: &str) -> &ParsedPart {
+        assert_eq!(
+            self.text(),
+            Some(text),
+            "unexpected text of part {:?}",
+            self.name
+        );
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{MultiPartFormDataBuilder, MultiPartFormDataParser};
+
+    fn parsed() -> crate::ParsedMultiPartFormData {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder.with_file(
+            "tests/sample.png",
+            "avatar",
+            "image/png",
+            "sample.png",
+        );
+        multipart_form_data_builder.with_text("name", "bob");
+        let ((_, content_type), body) = multipart_form_data_builder.build();
+        MultiPartFormDataParser::parse(&content_type, &body).unwrap()
+    }
+
+    #[test]
+    fn test_should_chain_assertions() {
+        let len = std::fs::read("tests/sample.png").unwrap().len();
+        parsed()
+            .assert_field("name", "bob")
+            .assert_no_part("missing")
+            .assert_part("avatar")
+            .assert_content_type("image/png")
+            .assert_file_name("sample.png")
+            .assert_len(len);
+    }
+
+    #[test]
+    #[should_panic(expected = "expected part \"missing\"")]
+    fn test_should_panic_on_missing_part() {
+        parsed().assert_part("missing");
+    }
+}
@@ -0,0 +1,115 @@
+use std::{
+    io::{self, Read},
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use bytes::Bytes;
+use futures_core::Stream;
+
+use crate::{MultiPartFormDataBuilder, MultiPartFormDataReader};
+
+/// Stream over a multipart/form-data body, e.g. for awc send_stream
+///
+/// Chunks are read from a MultiPartFormDataReader, the stream ends with a ConnectionAborted
+/// error once the token set with with_cancellation is cancelled
+pub struct MultiPartFormDataStream {
+    reader: MultiPartFormDataReader,
+    chunk_size: usize,
+    done: bool,
+}
+
+impl Stream for MultiPartFormDataStream {
+    type Item = io::Result<Bytes>;
+
+    fn poll_next(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.done {
+            return Poll::Ready(None);
+        }
+        let mut chunk = vec![0; self.chunk_size.max(1)];
+        match self.reader.read(&mut chunk) {
+            Ok(0) => {
+                self.done = true;
+                Poll::Ready(None)
+            }
+            Ok(read) => {
+                chunk.truncate(read);
+                Poll::Ready(Some(Ok(chunk.into())))
+            }
+            Err(error) => {
+                self.done = true;
+                Poll::Ready(Some(Err(error)))
+            }
+        }
+    }
+}
+
+impl MultiPartFormDataBuilder {
+    /// Build multipart/form-data as a stream of chunks
+    ///
+    /// chunk_size is maximum number of body bytes per chunk
+    ///
+    /// Requires "actix-test" feature
+    ///
+    /// Returns ((header_name, header_value), MultiPartFormDataStream)
+    pub fn build_stream(&self, chunk_size: usize) -> ((String, String), MultiPartFormDataStream) {
+        let (header, reader) = self.build_reader();
+        (
+            header,
+            MultiPartFormDataStream {
+                reader,
+                chunk_size,
+                done: false,
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future::poll_fn;
+
+    use super::*;
+    use crate::{CancellationToken, MultiPartFormDataParser};
+
+    async fn next(stream: &mut MultiPartFormDataStream) -> Option<io::Result<Bytes>> {
+        poll_fn(|cx| Pin::new(&mut *stream).poll_next(cx)).await
+    }
+
+    #[actix_web::test]
+    async fn test_should_stream_body_in_chunks() {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder
+            .with_file("tests/sample.png", "sample", "image/png", "sample.png")
+            .with_boundary("boundary");
+        let ((_, content_type), mut stream) = multipart_form_data_builder.build_stream(1024);
+
+        let mut body = vec![];
+        while let Some(chunk) = next(&mut stream).await {
+            let chunk = chunk.unwrap();
+            assert!(chunk.len() <= 1024);
+            body.extend_from_slice(&chunk);
+        }
+        assert_eq!(body, multipart_form_data_builder.build().1);
+        MultiPartFormDataParser::parse(&content_type, &body)
+            .unwrap()
+            .assert_part("sample")
+            .assert_data(std::fs::read("tests/sample.png").unwrap());
+    }
+
+    #[actix_web::test]
+    async fn test_should_abort_stream_when_cancelled() {
+        let cancellation_token = CancellationToken::new();
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder
+            .with_file("tests/sample.png", "sample", "image/png", "sample.png")
+            .with_cancellation(cancellation_token.clone());
+        let (_, mut stream) = multipart_form_data_builder.build_stream(1024);
+
+        assert!(next(&mut stream).await.unwrap().is_ok());
+        cancellation_token.cancel();
+        let error = next(&mut stream).await.unwrap().unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::ConnectionAborted);
+        assert!(next(&mut stream).await.is_none());
+    }
+}
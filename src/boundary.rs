@@ -0,0 +1,267 @@
+use std::fmt;
+
+use crate::BuildError;
+
+/// Maximum boundary length allowed by RFC 2046
+pub const MAX_BOUNDARY_LEN: usize = 70;
+
+/// Error returned for boundaries not allowed by RFC 2046
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BoundaryError {
+    /// Boundary is empty
+    Empty,
+    /// Boundary is longer than 70 characters, holds its length
+    TooLong(usize),
+    /// Boundary ends with a space
+    TrailingSpace,
+    /// Boundary contains a character outside the RFC 2046 bchars set
+    InvalidCharacter(char),
+}
+
+impl fmt::Display for BoundaryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BoundaryError::Empty => write!(f, "boundary is empty"),
+            BoundaryError::TooLong(len) => write!(
+                f,
+                "boundary has {} characters, at most {} are allowed",
+                len, MAX_BOUNDARY_LEN
+            ),
+            BoundaryError::TrailingSpace => write!(f, "boundary ends with a space"),
+            BoundaryError::InvalidCharacter(character) => {
+                write!(f, "boundary contains invalid character {:?}", character)
+            }
+        }
+    }
+}
+
+impl std::error::Error for BoundaryError {}
+
+/// Validate boundary against RFC 2046, 1 to 70 characters of digits, letters and '()+_,-./:=? or
+/// space, not ending with a space
+pub fn validate_boundary(boundary: &str) -> Result<(), BoundaryError> {
+    if let Some(character) = boundary.chars().find(|character| {
+        !character.is_ascii_alphanumeric() && !"'()+_,-./:=? ".contains(*character)
+    }) {
+        return Err(BoundaryError::InvalidCharacter(character));
+    }
+    match boundary.len() {
+        0 => Err(BoundaryError::Empty),
+        len if len > MAX_BOUNDARY_LEN => Err(BoundaryError::TooLong(len)),
+        _ if boundary.ends_with(' ') => Err(BoundaryError::TrailingSpace),
+        _ => Ok(()),
+    }
+}
+
+/// Boundary parameter value, quoted when boundary contains characters not allowed in a token
+pub(crate) fn boundary_parameter(boundary: &str) -> String {
+    match boundary.contains(|character| "()<>@,;:\\\"/[]?= ".contains(character)) {
+        true => format!(
+            "\"{}\"",
+            boundary.replace('\\', "\\\\").replace('"', "\\\"")
+        ),
+        false => boundary.to_string(),
+    }
+}
+
+/// Length of random boundaries, 36 for uuid v4, 32 hex digits otherwise
+pub(crate) const RANDOM_BOUNDARY_LEN: usize = if cfg!(feature = "uuid") { 36 } else { 32 };
+
+/// Fixed boundary checked against RFC 2046, random boundary when None, for the multipart
+/// builders besides MultiPartFormDataBuilder
+///
+/// Panics when boundary is invalid, like MultiPartFormDataBuilder builds
+pub(crate) fn checked_or_random_boundary(boundary: Option<&str>) -> String {
+    match boundary {
+        Some(boundary) => match validate_boundary(boundary) {
+            Ok(()) => boundary.to_string(),
+            Err(error) => panic!(
+                "{}",
+                BuildError::InvalidBoundary {
+                    boundary: boundary.to_string(),
+                    error,
+                }
+            ),
+        },
+        None => random_boundary(),
+    }
+}
+
+/// Generate a random boundary
+///
+/// Uses uuid v4 with the "uuid" feature, a randomly seeded hash otherwise
+///
+/// The hash seed is fixed on wasm32-unknown-unknown, boundaries are then distinct within a run
+/// but the same in every run
+pub(crate) fn random_boundary() -> String {
+    #[cfg(feature = "uuid")]
+    {
+        uuid::Uuid::new_v4().to_string()
+    }
+    #[cfg(not(feature = "uuid"))]
+    {
+        use std::{
+            collections::hash_map::RandomState,
+            hash::{BuildHasher, Hasher},
+            sync::atomic::{AtomicU64, Ordering},
+        };
+
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+        let state = RandomState::new();
+        let mut high = state.build_hasher();
+        high.write_u64(COUNTER.fetch_add(1, Ordering::Relaxed));
+        let mut low = state.build_hasher();
+        low.write_u64(high.finish());
+        format!("{:016x}{:016x}", high.finish(), low.finish())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MultiPartFormDataBuilder, MultiPartFormDataParser};
+
+    #[test]
+    fn test_should_validate_boundaries() {
+        assert_eq!(validate_boundary("simple boundary"), Ok(()));
+        assert_eq!(validate_boundary(&"b".repeat(70)), Ok(()));
+        assert_eq!(validate_boundary(""), Err(BoundaryError::Empty));
+        assert_eq!(
+            validate_boundary(&"b".repeat(71)),
+            Err(BoundaryError::TooLong(71))
+        );
+        assert_eq!(
+            validate_boundary("boundary "),
+            Err(BoundaryError::TrailingSpace)
+        );
+        assert_eq!(
+            validate_boundary("bound@ry"),
+            Err(BoundaryError::InvalidCharacter('@'))
+        );
+        assert!(validate_boundary(&random_boundary()).is_ok());
+    }
+
+    #[test]
+    fn test_should_quote_boundary_only_when_needed() {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder
+            .with_text("name", "some_name")
+            .try_with_boundary("simple boundary:1")
+            .unwrap();
+        let ((_, content_type), body) = multipart_form_data_builder.build();
+
+        assert_eq!(
+            content_type,
+            "multipart/form-data; boundary=\"simple boundary:1\""
+        );
+        MultiPartFormDataParser::parse(&content_type, &body)
+            .unwrap()
+            .assert_field("name", "some_name");
+        assert_eq!(boundary_parameter("simple-boundary_1"), "simple-boundary_1");
+    }
+
+    #[test]
+    fn test_should_reject_invalid_boundary() {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        let error = match multipart_form_data_builder.try_with_boundary("bound@ry") {
+            Ok(_) => panic!("invalid boundary is accepted"),
+            Err(error) => error,
+        };
+
+        assert_eq!(error.to_string(), "boundary contains invalid character '@'");
+        assert_eq!(multipart_form_data_builder.boundary(), None);
+    }
+
+    #[test]
+    fn test_should_generate_distinct_boundaries() {
+        assert_ne!(random_boundary(), random_boundary());
+    }
+
+    #[test]
+    fn test_should_use_boundary_generator() {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder
+            .with_text("name", "some_name")
+            .with_boundary_generator(|| "fixed-boundary".to_string());
+        let ((_, content_type), body) = multipart_form_data_builder.build();
+
+        assert_eq!(content_type, "multipart/form-data; boundary=fixed-boundary");
+        assert!(body.starts_with(b"--fixed-boundary\r\n"));
+    }
+
+    #[test]
+    fn test_should_expose_fixed_and_last_boundary() {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder.with_text("name", "some_name");
+        assert_eq!(multipart_form_data_builder.boundary(), None);
+
+        let ((_, content_type), _) = multipart_form_data_builder.build();
+        let boundary = multipart_form_data_builder.boundary().unwrap();
+        assert_eq!(
+            content_type,
+            format!("multipart/form-data; boundary={}", boundary)
+        );
+
+        multipart_form_data_builder.with_boundary("fixed-boundary");
+        assert_eq!(
+            multipart_form_data_builder.boundary().as_deref(),
+            Some("fixed-boundary")
+        );
+        let (_, body) = multipart_form_data_builder.build();
+        assert!(body.starts_with(b"--fixed-boundary\r\n"));
+    }
+
+    #[test]
+    fn test_should_keep_last_boundary_of_builds_only() {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder
+            .with_text("name", "some_name")
+            .with_boundary_generator(|| "generated".to_string());
+
+        multipart_form_data_builder.to_string();
+        multipart_form_data_builder.stats();
+        multipart_form_data_builder.to_curl_command("http://localhost/upload");
+        assert_eq!(multipart_form_data_builder.boundary(), None);
+
+        multipart_form_data_builder.build_parts();
+        assert_eq!(
+            multipart_form_data_builder.boundary().as_deref(),
+            Some("generated")
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "invalid boundary \"bound@ry\": boundary contains invalid character '@'"
+    )]
+    fn test_should_panic_on_build_with_invalid_boundary() {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder
+            .with_text("name", "some_name")
+            .with_boundary("bound@ry");
+
+        multipart_form_data_builder.build();
+    }
+
+    #[test]
+    #[should_panic(expected = "boundary ends with a space")]
+    fn test_should_panic_on_invalid_generated_boundary() {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder.with_boundary_generator(|| "boundary ".to_string());
+
+        multipart_form_data_builder.build();
+    }
+
+    #[test]
+    fn test_should_return_error_on_try_build_with_invalid_boundary() {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder.with_boundary("b".repeat(71));
+
+        match multipart_form_data_builder.try_build() {
+            Err(BuildError::InvalidBoundary { error, .. }) => {
+                assert_eq!(error, BoundaryError::TooLong(71))
+            }
+            _ => panic!("invalid boundary is accepted"),
+        }
+    }
+}
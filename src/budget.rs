@@ -0,0 +1,221 @@
+use std::fmt;
+
+use crate::{part::Part, BoundaryError, MultiPartFormDataBuilder};
+
+/// Error returned by MultiPartFormDataBuilder::try_build
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BuildError {
+    /// Body would be larger than the size set with with_max_total_size
+    TooLarge {
+        /// body length in bytes
+        size: usize,
+        /// maximum body length in bytes
+        max_total_size: usize,
+        /// name and content length of the largest part, None without parts
+        largest_part: Option<(String, usize)>,
+    },
+    /// Part has a Content-Transfer-Encoding header with ComplianceMode::Strict
+    ContentTransferEncoding {
+        /// part name
+        part: String,
+        /// header value
+        encoding: String,
+    },
+    /// Field name has special characters with SpecialCharacterPolicy::Reject
+    SpecialCharacters {
+        /// part name
+        part: String,
+    },
+    /// File name has control characters with ControlCharacterPolicy::Reject
+    ControlCharacters {
+        /// part name
+        part: String,
+        /// file name
+        file_name: String,
+    },
+    /// Boundary set with with_boundary or a boundary generator is not allowed by RFC 2046
+    InvalidBoundary {
+        /// boundary
+        boundary: String,
+        /// reason the boundary is invalid
+        error: BoundaryError,
+    },
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuildError::TooLarge {
+                size,
+                max_total_size,
+                largest_part,
+            } => {
+                write!(
+                    f,
+                    "body of {} bytes exceeds maximum total size of {} bytes",
+                    size, max_total_size
+                )?;
+                match largest_part {
+                    Some((name, len)) => {
+                        write!(f, ", largest part is `{}` with {} bytes", name, len)
+                    }
+                    None => Ok(()),
+                }
+            }
+            BuildError::ContentTransferEncoding { part, encoding } => write!(
+                f,
+                "part `{}` has Content-Transfer-Encoding `{}`, deprecated for multipart/form-data by RFC 7578",
+                part, encoding
+            ),
+            BuildError::SpecialCharacters { part } => write!(
+                f,
+                "field name `{}` has special characters, rejected by SpecialCharacterPolicy::Reject",
+                part
+            ),
+            BuildError::ControlCharacters { part, file_name } => write!(
+                f,
+                "file name {:?} of part `{}` has control characters, rejected by ControlCharacterPolicy::Reject",
+                file_name, part
+            ),
+            BuildError::InvalidBoundary { boundary, error } => write!(
+                f,
+                "invalid boundary {:?}: {}, see RFC 2046",
+                boundary, error
+            ),
+        }
+    }
+}
+
+impl std::error::Error for BuildError {}
+
+impl MultiPartFormDataBuilder {
+    /// Set a maximum body length, e.g. so a wrong fixture path never builds a multi GB body
+    ///
+    /// max_total_size is maximum body length in bytes, measured before building so file
+    /// contents are not read when it is exceeded
+    ///
+    /// try_build returns BuildError::TooLarge when the body would be larger, every other build
+    /// panics, including readers, segments and chunked or raw HTTP exports
+    ///
+    /// Returns &mut MultiPartFormDataBuilder
+    pub fn with_max_total_size(&mut self, max_total_size: usize) -> &mut MultiPartFormDataBuilder {
+        self.max_total_size = Some(max_total_size);
+        self
+    }
+
+    /// Build multipart/form-data like build, returning an error instead of panicking
+    ///
+    /// Returns ((header_name, header_value), body) or BuildError
+    pub fn try_build(&self) -> Result<((String, String), Vec<u8>), BuildError> {
+        let mut body = vec![];
+        let header = self.try_build_into(&mut body)?;
+        Ok((header, body))
+    }
+}
+
+/// Check body length of parts against max_total_size
+pub(crate) fn check_total_size(
+    boundary: &str,
+    parts: &[Part],
+    max_total_size: usize,
+) -> Result<(), BuildError> {
+    let size = parts
+        .iter()
+        .map(|part| part.encoded_len(boundary))
+        .sum::<usize>()
+        + boundary.len()
+        + 6;
+    match size > max_total_size {
+        true => Err(BuildError::TooLarge {
+            size,
+            max_total_size,
+            largest_part: parts
+                .iter()
+                .map(|part| (part.content_len(), part))
+                .max_by_key(|(len, _)| *len)
+                .map(|(len, part)| (String::from_utf8_lossy(&part.name).into_owned(), len)),
+        }),
+        false => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn multipart_form_data_builder() -> MultiPartFormDataBuilder {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder
+            .with_boundary("boundary")
+            .with_text("name", "some_name")
+            .with_file("tests/sample.png", "sample", "image/png", "sample.png");
+        multipart_form_data_builder
+    }
+
+    #[test]
+    fn test_should_build_body_within_max_total_size() {
+        let mut multipart_form_data_builder = multipart_form_data_builder();
+        let (_, body) = multipart_form_data_builder.build();
+        multipart_form_data_builder.with_max_total_size(body.len());
+
+        assert_eq!(multipart_form_data_builder.try_build().unwrap().1, body);
+    }
+
+    #[test]
+    fn test_should_fail_when_body_exceeds_max_total_size() {
+        let mut multipart_form_data_builder = multipart_form_data_builder();
+        let (_, body) = multipart_form_data_builder.build();
+        multipart_form_data_builder.with_max_total_size(1024);
+
+        let sample_len = std::fs::metadata("tests/sample.png").unwrap().len() as usize;
+
+        let error = multipart_form_data_builder.try_build().unwrap_err();
+        assert_eq!(
+            error,
+            BuildError::TooLarge {
+                size: body.len(),
+                max_total_size: 1024,
+                largest_part: Some(("sample".to_string(), sample_len)),
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            format!(
+                "body of {} bytes exceeds maximum total size of 1024 bytes, largest part is `sample` with {} bytes",
+                body.len(),
+                sample_len
+            )
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "exceeds maximum total size of 1024 bytes")]
+    fn test_should_panic_on_build_when_body_exceeds_max_total_size() {
+        multipart_form_data_builder()
+            .with_max_total_size(1024)
+            .build();
+    }
+
+    #[test]
+    fn test_should_check_max_total_size_in_every_build() {
+        let mut multipart_form_data_builder = multipart_form_data_builder();
+        multipart_form_data_builder.with_max_total_size(1024);
+        let builds: [&dyn Fn(&MultiPartFormDataBuilder); 4] = [
+            &|builder| drop(builder.build_reader()),
+            &|builder| drop(builder.build_to_writer(&mut std::io::sink())),
+            &|builder| drop(builder.build_parts()),
+            &|builder| drop(builder.build_chunked(64)),
+        ];
+
+        for build in builds {
+            let panic = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                build(&multipart_form_data_builder)
+            }))
+            .unwrap_err();
+            assert!(panic
+                .downcast_ref::<String>()
+                .unwrap()
+                .contains("exceeds maximum total size of 1024 bytes"));
+        }
+    }
+}
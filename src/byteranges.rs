@@ -0,0 +1,165 @@
+use std::ops::RangeInclusive;
+
+use bytes::Bytes;
+
+use crate::{
+    boundary::{boundary_parameter, checked_or_random_boundary},
+    mime::write_multipart,
+    MimePart,
+};
+
+/// Simple builder for multipart/byteranges test, e.g. 206 Partial Content responses
+///
+/// Each range becomes a part with Content-Type and Content-Range of the source
+///
+/// # Examples
+///
+/// ```
+/// use actix_multipart_test::MultipartByteRangesBuilder;
+///
+/// let mut multipart_byte_ranges_builder =
+///     MultipartByteRangesBuilder::new("text/plain", "0123456789");
+/// multipart_byte_ranges_builder.with_range(0..=1).with_range(8..=9);
+/// let (header, body) = multipart_byte_ranges_builder.build();
+/// ```
+pub struct MultipartByteRangesBuilder {
+    content_type: String,
+    source: Bytes,
+    ranges: Vec<RangeInclusive<usize>>,
+    boundary: Option<String>,
+}
+
+impl MultipartByteRangesBuilder {
+    /// Create new MultipartByteRangesBuilder
+    ///
+    /// content_type is Content-Type of every part
+    ///
+    /// source is complete representation ranges are taken from
+    pub fn new(
+        content_type: impl Into<String>,
+        source: impl Into<Bytes>,
+    ) -> MultipartByteRangesBuilder {
+        MultipartByteRangesBuilder {
+            content_type: content_type.into(),
+            source: source.into(),
+            ranges: vec![],
+            boundary: None,
+        }
+    }
+
+    /// Set a fixed boundary used by every build, e.g. for snapshot or golden tests
+    ///
+    /// boundary is checked against RFC 2046 on build, builds panic when it is invalid
+    ///
+    /// Returns &mut MultipartByteRangesBuilder
+    pub fn with_boundary(
+        &mut self,
+        boundary: impl Into<String>,
+    ) -> &mut MultipartByteRangesBuilder {
+        self.boundary = Some(boundary.into());
+        self
+    }
+
+    /// Add range, bounds are inclusive like in Range header, e.g. 0..=99 are first 100 bytes
+    ///
+    /// Panics on build when range is out of source bounds
+    ///
+    /// Returns &mut MultipartByteRangesBuilder
+    pub fn with_range(&mut self, range: RangeInclusive<usize>) -> &mut MultipartByteRangesBuilder {
+        self.ranges.push(range);
+        self
+    }
+
+    /// Build multipart/byteranges
+    ///
+    /// Returns ((header_name, header_value), body)
+    ///
+    /// header_name is "Content-Type"
+    ///
+    /// header_value is "multipart/byteranges; boundary=..."
+    ///
+    /// body is binary data
+    pub fn build(&self) -> ((String, String), Vec<u8>) {
+        let boundary = checked_or_random_boundary(self.boundary.as_deref());
+        let parts: Vec<MimePart> = self
+            .ranges
+            .iter()
+            .map(|range| {
+                MimePart::new(self.content_type.clone(), self.source.slice(range.clone()))
+                    .with_header(
+                        "Content-Range",
+                        format!(
+                            "bytes {}-{}/{}",
+                            range.start(),
+                            range.end(),
+                            self.source.len()
+                        ),
+                    )
+            })
+            .collect();
+        let header = (
+            "Content-Type".to_string(),
+            format!(
+                "multipart/byteranges; boundary={}",
+                boundary_parameter(&boundary)
+            ),
+        );
+        (header, write_multipart(&boundary, &parts))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MultiPartFormDataParser;
+
+    #[test]
+    fn test_should_build_multipart_byte_ranges() {
+        let mut multipart_byte_ranges_builder =
+            MultipartByteRangesBuilder::new("text/plain", "0123456789");
+        multipart_byte_ranges_builder
+            .with_range(0..=1)
+            .with_range(8..=9);
+        let ((_, content_type), body) = multipart_byte_ranges_builder.build();
+
+        assert!(content_type.starts_with("multipart/byteranges; boundary="));
+        let parsed = MultiPartFormDataParser::parse(&content_type, &body).unwrap();
+        parsed.parts[0]
+            .assert_content_type("text/plain")
+            .assert_header("Content-Range", "bytes 0-1/10")
+            .assert_text("01");
+        parsed.parts[1]
+            .assert_header("Content-Range", "bytes 8-9/10")
+            .assert_text("89");
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_should_panic_on_range_out_of_bounds() {
+        MultipartByteRangesBuilder::new("text/plain", "0123456789")
+            .with_range(5..=10)
+            .build();
+    }
+
+    #[test]
+    fn test_should_build_multipart_byte_ranges_with_fixed_boundary() {
+        let mut multipart_byte_ranges_builder =
+            MultipartByteRangesBuilder::new("text/plain", "0123456789");
+        multipart_byte_ranges_builder
+            .with_range(0..=1)
+            .with_boundary("ranges");
+        let ((_, content_type), body) = multipart_byte_ranges_builder.build();
+
+        assert_eq!(content_type, "multipart/byteranges; boundary=ranges");
+        assert_eq!(body, multipart_byte_ranges_builder.build().1);
+        assert!(body.starts_with(b"--ranges\r\n"));
+    }
+
+    #[test]
+    #[should_panic(expected = "boundary has 71 characters")]
+    fn test_should_panic_on_invalid_byte_ranges_boundary() {
+        MultipartByteRangesBuilder::new("text/plain", "0123456789")
+            .with_boundary("b".repeat(71))
+            .build();
+    }
+}
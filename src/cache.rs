@@ -0,0 +1,110 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::SystemTime,
+};
+
+/// File contents cached across builds, keyed by path, modification time and length
+#[derive(Default)]
+pub(crate) struct FileCache {
+    files: Mutex<HashMap<PathBuf, CachedFile>>,
+}
+
+struct CachedFile {
+    modified: SystemTime,
+    len: u64,
+    content: Arc<[u8]>,
+}
+
+impl FileCache {
+    /// Cached content of path, file is read again when its modification time or length changed
+    pub(crate) fn get(&self, path: &Path) -> Arc<[u8]> {
+        let metadata = std::fs::metadata(path).unwrap();
+        let modified = metadata.modified().unwrap();
+        let mut files = self.files.lock().unwrap();
+        match files.get(path) {
+            Some(cached) if cached.modified == modified && cached.len == metadata.len() => {
+                cached.content.clone()
+            }
+            _ => {
+                let content: Arc<[u8]> = std::fs::read(path).unwrap().into();
+                files.insert(
+                    path.to_path_buf(),
+                    CachedFile {
+                        modified,
+                        len: metadata.len(),
+                        content: content.clone(),
+                    },
+                );
+                content
+            }
+        }
+    }
+
+    /// Remove all cached contents
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    pub(crate) fn clear(&self) {
+        self.files.lock().unwrap().clear();
+    }
+
+    #[cfg(test)]
+    pub(crate) fn len(&self) -> usize {
+        self.files.lock().unwrap().len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MultiPartFormDataBuilder;
+
+    fn fixture(name: &str, content: &[u8]) -> PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "actix-multipart-test-cache-{}-{}",
+            std::process::id(),
+            name
+        ));
+        std::fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_should_cache_file_until_changed() {
+        let path = fixture("changed", b"first");
+        let cache = FileCache::default();
+
+        assert_eq!(&*cache.get(&path), b"first");
+        assert_eq!(cache.len(), 1);
+        std::fs::write(&path, b"second content").unwrap();
+        assert_eq!(&*cache.get(&path), b"second content");
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_should_read_file_again_after_invalidate() {
+        let path = fixture("invalidate", b"first");
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder.with_file_cache().with_file(
+            path.clone(),
+            "file",
+            "text/plain",
+            "file.txt",
+        );
+
+        let (_, first) = multipart_form_data_builder.build();
+        std::fs::write(&path, b"other").unwrap();
+        multipart_form_data_builder.invalidate_file_cache();
+        let (_, second) = multipart_form_data_builder.build();
+
+        assert!(String::from_utf8(first)
+            .unwrap()
+            .contains("\r\n\r\nfirst\r\n"));
+        assert!(String::from_utf8(second)
+            .unwrap()
+            .contains("\r\n\r\nother\r\n"));
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,59 @@
+use std::{
+    io,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+/// Token aborting in flight uploads, e.g. to test server side cleanup of partial uploads
+///
+/// Clones share the same state, cancel from any thread
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Read;
+///
+/// use actix_multipart_test::{CancellationToken, MultiPartFormDataBuilder};
+///
+/// let cancellation_token = CancellationToken::new();
+/// let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+/// multipart_form_data_builder
+///     .with_text("name", "some_name")
+///     .with_cancellation(cancellation_token.clone());
+/// let (_, mut reader) = multipart_form_data_builder.build_reader();
+///
+/// cancellation_token.cancel();
+/// assert!(reader.read(&mut [0; 16]).is_err());
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    /// Create new CancellationToken, not cancelled
+    pub fn new() -> CancellationToken {
+        CancellationToken::default()
+    }
+
+    /// Cancel uploads using this token
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    /// Returns true when cancel was called
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+
+    /// Returns ConnectionAborted error when cancelled
+    pub(crate) fn check(&self) -> io::Result<()> {
+        if self.is_cancelled() {
+            return Err(io::Error::new(
+                io::ErrorKind::ConnectionAborted,
+                "upload cancelled",
+            ));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,176 @@
+use crate::{MultiPartFormData, MultiPartFormDataBuilder};
+
+impl MultiPartFormDataBuilder {
+    /// Build multipart/form-data with HTTP/1.1 chunked transfer coding, e.g. for the raw request
+    /// and TCP helpers
+    ///
+    /// chunk_size is maximum number of body bytes per chunk
+    ///
+    /// Returns MultiPartFormData with Content-Type, Content-Encoding when body encoding is set,
+    /// Transfer-Encoding and request headers, body is encoded and framed in chunks with hex sizes
+    /// and a terminating zero size chunk
+    pub fn build_chunked(&self, chunk_size: usize) -> MultiPartFormData {
+        self.build_chunked_with_trailers(chunk_size, |_| vec![])
+    }
+
+    /// Build multipart/form-data with HTTP/1.1 chunked transfer coding and trailer headers
+    ///
+    /// trailers is called with the encoded unframed body, e.g. to compute a checksum, and returns
+    /// (name, value) trailer headers written after the last chunk
+    ///
+    /// Returns MultiPartFormData as build_chunked, with a Trailer header listing trailer names
+    /// when there are trailers
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use actix_multipart_test::{MultiPartFormDataBuilder, PayloadDigest};
+    ///
+    /// let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+    /// multipart_form_data_builder.with_text("name", "some_name");
+    /// let form_data = multipart_form_data_builder.build_chunked_with_trailers(1024, |body| {
+    ///     vec![("X-Checksum".to_string(), PayloadDigest::of(body).sha256)]
+    /// });
+    /// assert_eq!(form_data.header("Trailer"), Some("X-Checksum"));
+    /// ```
+    pub fn build_chunked_with_trailers(
+        &self,
+        chunk_size: usize,
+        trailers: impl FnOnce(&[u8]) -> Vec<(String, String)>,
+    ) -> MultiPartFormData {
+        let (content_type, body) = self.build();
+        let body = self.body_encoding.encode(body);
+        let trailers = trailers(&body);
+        let mut headers = vec![content_type];
+        if let Some(content_encoding) = self.body_encoding.header_value() {
+            headers.push(("Content-Encoding".to_string(), content_encoding.to_string()));
+        }
+        headers.push(("Transfer-Encoding".to_string(), "chunked".to_string()));
+        if !trailers.is_empty() {
+            let names: Vec<&str> = trailers.iter().map(|(name, _)| name.as_str()).collect();
+            headers.push(("Trailer".to_string(), names.join(", ")));
+        }
+        headers.extend(self.request_headers.iter().cloned());
+        MultiPartFormData {
+            headers,
+            body: chunked(&body, chunk_size, &trailers),
+        }
+    }
+}
+
+/// Frame body in chunks of at most chunk_size bytes, followed by the last chunk, trailers and a
+/// blank line
+pub(crate) fn chunked(body: &[u8], chunk_size: usize, trailers: &[(String, String)]) -> Vec<u8> {
+    let mut framed = Vec::with_capacity(body.len() + body.len() / chunk_size.max(1) * 8 + 5);
+    for chunk in body.chunks(chunk_size.max(1)) {
+        framed.extend(format!("{:x}\r\n", chunk.len()).as_bytes());
+        framed.extend_from_slice(chunk);
+        framed.extend(b"\r\n");
+    }
+    framed.extend(b"0\r\n");
+    for (name, value) in trailers.iter() {
+        framed.extend(format!("{}: {}\r\n", name, value).as_bytes());
+    }
+    framed.extend(b"\r\n");
+    framed
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use super::*;
+    use crate::{BodyEncoding, MultiPartFormDataParser, PayloadDigest};
+
+    /// Reassemble chunked body
+    fn unchunk(mut framed: &[u8]) -> Vec<u8> {
+        let mut body = vec![];
+        loop {
+            let line_end = framed
+                .windows(2)
+                .position(|window| window == b"\r\n")
+                .unwrap();
+            let size = usize::from_str_radix(std::str::from_utf8(&framed[..line_end]).unwrap(), 16)
+                .unwrap();
+            framed = &framed[line_end + 2..];
+            if size == 0 {
+                assert_eq!(framed, b"\r\n");
+                return body;
+            }
+            body.extend_from_slice(&framed[..size]);
+            assert_eq!(&framed[size..size + 2], b"\r\n");
+            framed = &framed[size + 2..];
+        }
+    }
+
+    #[test]
+    fn test_should_frame_body_in_chunks() {
+        assert_eq!(
+            chunked(b"0123456789abcdefghij", 16, &[]),
+            b"10\r\n0123456789abcdef\r\n4\r\nghij\r\n0\r\n\r\n"
+        );
+        assert_eq!(chunked(b"", 16, &[]), b"0\r\n\r\n");
+    }
+
+    #[test]
+    fn test_should_build_chunked() {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder.with_file(
+            "tests/sample.png",
+            "sample",
+            "image/png",
+            "sample.png",
+        );
+        let form_data = multipart_form_data_builder.build_chunked(100);
+
+        assert_eq!(form_data.header("Transfer-Encoding"), Some("chunked"));
+        assert_eq!(form_data.header("Content-Length"), None);
+        let body = unchunk(&form_data.body);
+        let parsed =
+            MultiPartFormDataParser::parse(form_data.header("Content-Type").unwrap(), &body)
+                .unwrap();
+        parsed
+            .assert_part("sample")
+            .assert_data(std::fs::read("tests/sample.png").unwrap());
+    }
+
+    #[test]
+    fn test_should_encode_body_before_framing() {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder
+            .with_text("name", "some_name")
+            .with_boundary("boundary")
+            .with_body_encoding(BodyEncoding::Gzip);
+        let (_, body) = multipart_form_data_builder.build();
+        let form_data = multipart_form_data_builder.build_chunked(16);
+
+        assert_eq!(form_data.header("Content-Encoding"), Some("gzip"));
+        let mut decoded = vec![];
+        flate2::read::GzDecoder::new(unchunk(&form_data.body).as_slice())
+            .read_to_end(&mut decoded)
+            .unwrap();
+        assert_eq!(decoded, body);
+    }
+
+    #[test]
+    fn test_should_write_trailers_after_last_chunk() {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder
+            .with_text("name", "some_name")
+            .with_boundary("boundary");
+        let (_, body) = multipart_form_data_builder.build();
+        let form_data = multipart_form_data_builder.build_chunked_with_trailers(16, |body| {
+            vec![
+                ("X-Checksum".to_string(), PayloadDigest::of(body).sha256),
+                ("X-Parts".to_string(), "1".to_string()),
+            ]
+        });
+
+        assert_eq!(form_data.header("Trailer"), Some("X-Checksum, X-Parts"));
+        let trailers = format!(
+            "\r\n0\r\nX-Checksum: {}\r\nX-Parts: 1\r\n\r\n",
+            PayloadDigest::of(&body).sha256
+        );
+        assert!(form_data.body.ends_with(trailers.as_bytes()));
+    }
+}
@@ -0,0 +1,126 @@
+use crate::{budget::BuildError, part::Part, MultiPartFormDataBuilder};
+
+/// Handling of features deprecated for multipart/form-data by RFC 7578, checked on build
+///
+/// Content-Transfer-Encoding part headers, e.g. added with Part::base64, are deprecated
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ComplianceMode {
+    /// Deprecated features are written as requested, e.g. to simulate legacy clients
+    #[default]
+    Lenient,
+    /// Deprecated features are written with a tracing warning, requires "tracing" feature
+    Warn,
+    /// try_build returns BuildError::ContentTransferEncoding, every other build panics, Display
+    /// and stats render parts unchecked
+    Strict,
+}
+
+impl MultiPartFormDataBuilder {
+    /// Set compliance mode for features deprecated by RFC 7578
+    ///
+    /// compliance_mode is compliance mode, default is Lenient
+    ///
+    /// Returns &mut MultiPartFormDataBuilder
+    pub fn with_compliance_mode(
+        &mut self,
+        compliance_mode: ComplianceMode,
+    ) -> &mut MultiPartFormDataBuilder {
+        self.compliance_mode = compliance_mode;
+        self
+    }
+}
+
+/// Check parts against compliance mode, warnings are tracing events
+pub(crate) fn check_compliance(
+    parts: &[Part],
+    compliance_mode: ComplianceMode,
+) -> Result<(), BuildError> {
+    if compliance_mode == ComplianceMode::Lenient {
+        return Ok(());
+    }
+    for part in parts.iter() {
+        let encoding = part
+            .headers
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case("Content-Transfer-Encoding"));
+        if let Some((_, encoding)) = encoding {
+            let error = BuildError::ContentTransferEncoding {
+                part: String::from_utf8_lossy(&part.name).into_owned(),
+                encoding: encoding.clone(),
+            };
+            match compliance_mode {
+                ComplianceMode::Strict => return Err(error),
+                #[cfg(feature = "tracing")]
+                _ => tracing::warn!("{}", error),
+                #[cfg(not(feature = "tracing"))]
+                _ => {}
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MultiPartFormDataParser;
+
+    fn multipart_form_data_builder(compliance_mode: ComplianceMode) -> MultiPartFormDataBuilder {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder
+            .with_part(
+                Part::bytes("file", "data.bin", "application/octet-stream", "content").base64(),
+            )
+            .with_compliance_mode(compliance_mode);
+        multipart_form_data_builder
+    }
+
+    #[test]
+    fn test_should_allow_content_transfer_encoding_unless_strict() {
+        for compliance_mode in [ComplianceMode::Lenient, ComplianceMode::Warn] {
+            let ((_, content_type), body) = multipart_form_data_builder(compliance_mode)
+                .try_build()
+                .unwrap();
+
+            let parsed = MultiPartFormDataParser::parse(&content_type, &body).unwrap();
+            parsed.assert_part("file").assert_data("Y29udGVudA==");
+            assert!(String::from_utf8_lossy(&body)
+                .contains("\r\nContent-Transfer-Encoding: base64\r\n"));
+        }
+    }
+
+    #[test]
+    fn test_should_reject_content_transfer_encoding_when_strict() {
+        let error = multipart_form_data_builder(ComplianceMode::Strict)
+            .try_build()
+            .unwrap_err();
+
+        assert_eq!(
+            error,
+            BuildError::ContentTransferEncoding {
+                part: "file".to_string(),
+                encoding: "base64".to_string(),
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "part `file` has Content-Transfer-Encoding `base64`, deprecated for multipart/form-data by RFC 7578"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "part `file` has Content-Transfer-Encoding `base64`")]
+    fn test_should_reject_content_transfer_encoding_when_strict_in_reader() {
+        multipart_form_data_builder(ComplianceMode::Strict).build_reader();
+    }
+
+    #[test]
+    fn test_should_not_check_compliance_when_only_rendering() {
+        let multipart_form_data_builder = multipart_form_data_builder(ComplianceMode::Strict);
+
+        assert!(multipart_form_data_builder
+            .to_string()
+            .contains("\r\nContent-Transfer-Encoding: base64\r\n"));
+        assert_eq!(multipart_form_data_builder.stats().part_count, 1);
+    }
+}
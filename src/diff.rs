@@ -0,0 +1,183 @@
+use std::fmt;
+
+use crate::{
+    MultiPartFormDataBuilder, MultiPartFormDataParser, ParsedMultiPartFormData, ParsedPart,
+};
+
+/// Differences between two multipart bodies, parts are matched by name and occurrence
+///
+/// Display renders a report with one line per part, e.g. "field `lang` missing"
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultipartDiff {
+    /// part differences, parts of left body first in received order, then added parts
+    pub parts: Vec<PartDiff>,
+}
+
+/// Difference of a part
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartDiff {
+    /// part name, "<unnamed>" when part has no name
+    pub name: String,
+    /// true when part has a file name
+    pub is_file: bool,
+    /// kind of difference
+    pub change: PartChange,
+}
+
+/// Kind of part difference
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PartChange {
+    /// Part of left body is missing in right body
+    Missing,
+    /// Part of right body is not in left body
+    Added,
+    /// Part is in both bodies, e.g. "size differs: 10 != 12"
+    Changed(Vec<String>),
+}
+
+impl MultipartDiff {
+    /// Returns true when bodies have the same parts
+    pub fn is_empty(&self) -> bool {
+        self.parts.is_empty()
+    }
+}
+
+impl fmt::Display for MultipartDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for part in self.parts.iter() {
+            let kind = if part.is_file { "file" } else { "field" };
+            match &part.change {
+                PartChange::Missing => writeln!(f, "{} `{}` missing", kind, part.name)?,
+                PartChange::Added => writeln!(f, "{} `{}` added", kind, part.name)?,
+                PartChange::Changed(differences) => {
+                    for difference in differences.iter() {
+                        writeln!(f, "{} `{}` {}", kind, part.name, difference)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl ParsedMultiPartFormData {
+    /// Compare parts with other body, self is left and other is right
+    pub fn diff(&self, other: &ParsedMultiPartFormData) -> MultipartDiff {
+        let left = keyed(&self.parts);
+        let right = keyed(&other.parts);
+        let mut parts = vec![];
+        for (key, part) in left.iter() {
+            let change = match right.iter().find(|(other_key, _)| other_key == key) {
+                None => PartChange::Missing,
+                Some((_, other_part)) => {
+                    let differences = differences(part, other_part);
+                    if differences.is_empty() {
+                        continue;
+                    }
+                    PartChange::Changed(differences)
+                }
+            };
+            parts.push(part_diff(part, change));
+        }
+        for (key, part) in right.iter() {
+            if !left.iter().any(|(other_key, _)| other_key == key) {
+                parts.push(part_diff(part, PartChange::Added));
+            }
+        }
+        MultipartDiff { parts }
+    }
+}
+
+impl MultiPartFormDataBuilder {
+    /// Compare built parts with other builder, self is left and other is right
+    pub fn diff(&self, other: &MultiPartFormDataBuilder) -> MultipartDiff {
+        parsed(self).diff(&parsed(other))
+    }
+}
+
+fn parsed(builder: &MultiPartFormDataBuilder) -> ParsedMultiPartFormData {
+    let ((_, content_type), body) = builder.build();
+    MultiPartFormDataParser::parse(&content_type, &body).unwrap()
+}
+
+/// Pair parts with (name, occurrence of name)
+fn keyed(parts: &[ParsedPart]) -> Vec<((Option<&str>, usize), &ParsedPart)> {
+    let mut keyed: Vec<((Option<&str>, usize), &ParsedPart)> = vec![];
+    for part in parts.iter() {
+        let name = part.name.as_deref();
+        let occurrence = keyed
+            .iter()
+            .filter(|((other, _), _)| *other == name)
+            .count();
+        keyed.push(((name, occurrence), part));
+    }
+    keyed
+}
+
+fn part_diff(part: &ParsedPart, change: PartChange) -> PartDiff {
+    PartDiff {
+        name: part.name.clone().unwrap_or_else(|| "<unnamed>".to_string()),
+        is_file: part.file_name.is_some(),
+        change,
+    }
+}
+
+fn differences(left: &ParsedPart, right: &ParsedPart) -> Vec<String> {
+    let mut differences = vec![];
+    if left.file_name != right.file_name {
+        differences.push(format!(
+            "file name differs: {:?} != {:?}",
+            left.file_name, right.file_name
+        ));
+    }
+    if left.content_type != right.content_type {
+        differences.push(format!(
+            "content type differs: {:?} != {:?}",
+            left.content_type, right.content_type
+        ));
+    }
+    if left.data != right.data {
+        match (left.text(), right.text()) {
+            (Some(left_text), Some(right_text)) if left.file_name.is_none() => differences.push(
+                format!("value differs: {:?} != {:?}", left_text, right_text),
+            ),
+            _ if left.data.len() != right.data.len() => differences.push(format!(
+                "size differs: {} != {}",
+                left.data.len(),
+                right.data.len()
+            )),
+            _ => differences.push("content differs".to_string()),
+        }
+    }
+    differences
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_should_report_part_differences() {
+        let mut expected = MultiPartFormDataBuilder::new();
+        expected
+            .with_text("lang", "en")
+            .with_text("name", "some_name")
+            .with_bytes("doc", "doc.txt", "text/plain", "0123456789");
+        let mut actual = MultiPartFormDataBuilder::new();
+        actual
+            .with_text("name", "other_name")
+            .with_bytes("doc", "doc.txt", "text/plain", "012")
+            .with_text("tag", "new");
+
+        let diff = expected.diff(&actual);
+
+        assert_eq!(
+            diff.to_string(),
+            "field `lang` missing\n\
+             field `name` value differs: \"some_name\" != \"other_name\"\n\
+             file `doc` size differs: 10 != 3\n\
+             field `tag` added\n"
+        );
+        assert!(expected.diff(&expected).is_empty());
+    }
+}
@@ -0,0 +1,71 @@
+use sha2::{Digest, Sha256};
+
+use crate::{MultiPartFormData, MultiPartFormDataBuilder};
+
+/// Digests of a built multipart/form-data body
+///
+/// sha256 is lowercase hex SHA-256 of body
+///
+/// md5 is lowercase hex MD5 of body, requires "md5" feature
+pub struct PayloadDigest {
+    pub sha256: String,
+    #[cfg(feature = "md5")]
+    pub md5: String,
+}
+
+impl PayloadDigest {
+    /// Compute digests of body
+    pub fn of(body: &[u8]) -> PayloadDigest {
+        PayloadDigest {
+            sha256: to_hex(&Sha256::digest(body)),
+            #[cfg(feature = "md5")]
+            md5: to_hex(&md5::Md5::digest(body)),
+        }
+    }
+}
+
+impl MultiPartFormDataBuilder {
+    /// Build multipart/form-data and compute digests of body
+    ///
+    /// Returns (MultiPartFormData, PayloadDigest)
+    pub fn build_with_digest(&self) -> (MultiPartFormData, PayloadDigest) {
+        let form_data = self.build_form_data();
+        let digest = PayloadDigest::of(&form_data.body);
+        (form_data, digest)
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_should_compute_sha256_of_body() {
+        let digest = PayloadDigest::of(b"abc");
+        assert_eq!(
+            digest.sha256,
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[cfg(feature = "md5")]
+    #[test]
+    fn test_should_compute_md5_of_body() {
+        let digest = PayloadDigest::of(b"abc");
+        assert_eq!(digest.md5, "900150983cd24fb0d6963f7d28e17f72");
+    }
+
+    #[test]
+    fn test_should_build_with_digest() {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder.with_text("name", "some_name");
+        let (form_data, digest) = multipart_form_data_builder.build_with_digest();
+
+        assert_eq!(digest.sha256, PayloadDigest::of(&form_data.body).sha256);
+        assert_eq!(digest.sha256.len(), 64);
+    }
+}
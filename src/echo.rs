@@ -0,0 +1,134 @@
+use actix_web::{dev::HttpServiceFactory, http::header, web, HttpRequest, HttpResponse};
+use serde::{Deserialize, Serialize};
+
+use crate::{MultiPartFormDataParser, PayloadDigest};
+
+/// JSON summary returned by echo_multipart_service
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EchoSummary {
+    /// parts in received order
+    pub parts: Vec<EchoPart>,
+}
+
+/// JSON summary of one part returned by echo_multipart_service
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EchoPart {
+    pub name: Option<String>,
+    pub file_name: Option<String>,
+    pub content_type: Option<String>,
+    pub size: usize,
+    /// lowercase hex SHA-256 of part content
+    pub sha256: String,
+}
+
+/// Actix service echoing a JSON summary of the received multipart body
+///
+/// path is resource path, any method is accepted
+///
+/// Responds 200 with EchoSummary or 400 with {"error": "..."} when body is not parsable
+///
+/// # Examples
+///
+/// ```
+/// use actix_multipart_test::{echo_multipart_service, EchoSummary, MultiPartFormDataBuilder};
+/// use actix_web::{test, App};
+///
+/// #[actix_web::main]
+/// async fn main() {
+///     let app = test::init_service(App::new().service(echo_multipart_service("/echo"))).await;
+///
+///     let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+///     multipart_form_data_builder.with_text("name", "some_name");
+///     let (header, body) = multipart_form_data_builder.build();
+///
+///     let req = test::TestRequest::post()
+///         .uri("/echo")
+///         .insert_header(header)
+///         .set_payload(body)
+///         .to_request();
+///     let summary: EchoSummary = test::call_and_read_body_json(&app, req).await;
+///
+///     assert_eq!(summary.parts[0].name.as_deref(), Some("name"));
+/// }
+/// ```
+pub fn echo_multipart_service(path: &str) -> impl HttpServiceFactory {
+    web::resource(path).route(web::route().to(echo))
+}
+
+async fn echo(req: HttpRequest, body: web::Bytes) -> HttpResponse {
+    let content_type = req
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+    match MultiPartFormDataParser::parse(content_type, &body) {
+        Ok(parsed) => HttpResponse::Ok().json(EchoSummary {
+            parts: parsed
+                .parts
+                .into_iter()
+                .map(|part| EchoPart {
+                    size: part.data.len(),
+                    sha256: PayloadDigest::of(&part.data).sha256,
+                    name: part.name,
+                    file_name: part.file_name,
+                    content_type: part.content_type,
+                })
+                .collect(),
+        }),
+        Err(error) => {
+            HttpResponse::BadRequest().json(serde_json::json!({ "error": error.to_string() }))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{test, App};
+
+    use super::*;
+    use crate::MultiPartFormDataBuilder;
+
+    #[actix_web::test]
+    async fn test_should_echo_parts_summary() {
+        let app = test::init_service(App::new().service(echo_multipart_service("/echo"))).await;
+
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder.with_file(
+            "tests/sample.png",
+            "sample",
+            "image/png",
+            "sample.png",
+        );
+        multipart_form_data_builder.with_text("name", "some_name");
+        let (header, body) = multipart_form_data_builder.build();
+
+        let req = test::TestRequest::put()
+            .uri("/echo")
+            .insert_header(header)
+            .set_payload(body)
+            .to_request();
+        let summary: EchoSummary = test::call_and_read_body_json(&app, req).await;
+
+        let sample = std::fs::read("tests/sample.png").unwrap();
+        assert_eq!(summary.parts.len(), 2);
+        assert_eq!(summary.parts[0].file_name.as_deref(), Some("sample.png"));
+        assert_eq!(summary.parts[0].content_type.as_deref(), Some("image/png"));
+        assert_eq!(summary.parts[0].size, sample.len());
+        assert_eq!(summary.parts[0].sha256, PayloadDigest::of(&sample).sha256);
+        assert_eq!(summary.parts[1].name.as_deref(), Some("name"));
+    }
+
+    #[actix_web::test]
+    async fn test_should_reject_unparsable_body() {
+        let app = test::init_service(App::new().service(echo_multipart_service("/echo"))).await;
+
+        let req = test::TestRequest::post()
+            .uri("/echo")
+            .insert_header(("Content-Type", "text/plain"))
+            .set_payload("hello")
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+
+        assert_eq!(resp.status(), 400);
+    }
+}
@@ -0,0 +1,205 @@
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+use std::path::Path;
+
+use bytes::Bytes;
+
+use crate::{
+    boundary::random_boundary, mime::write_multipart, MimePart, MultipartAlternativeBuilder,
+};
+
+/// Simple builder for RFC 5322 email messages, e.g. for inbound email webhooks
+///
+/// Body is a single part without attachments, multipart/mixed with attachments, text and html
+/// bodies together are a multipart/alternative part
+///
+/// # Examples
+///
+/// ```
+/// use actix_multipart_test::EmailMimeBuilder;
+///
+/// let mut email_mime_builder = EmailMimeBuilder::new();
+/// email_mime_builder
+///     .with_header("From", "sender@example.com")
+///     .with_header("To", "inbox@example.com")
+///     .with_header("Subject", "Report")
+///     .with_text_body("See attached")
+///     .with_file_attachment("tests/sample.png", "image/png", "sample.png");
+/// let message = email_mime_builder.build();
+/// ```
+#[derive(Default)]
+pub struct EmailMimeBuilder {
+    headers: Vec<(String, String)>,
+    text_body: Option<String>,
+    html_body: Option<String>,
+    attachments: Vec<MimePart>,
+}
+
+impl EmailMimeBuilder {
+    /// Create new EmailMimeBuilder
+    pub fn new() -> EmailMimeBuilder {
+        EmailMimeBuilder {
+            headers: vec![],
+            text_body: None,
+            html_body: None,
+            attachments: vec![],
+        }
+    }
+
+    /// Add message header, e.g. From, To, Subject, Date or Message-ID
+    ///
+    /// Returns &mut EmailMimeBuilder
+    pub fn with_header(
+        &mut self,
+        name: impl Into<String>,
+        value: impl Into<String>,
+    ) -> &mut EmailMimeBuilder {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Set text/plain body
+    ///
+    /// Returns &mut EmailMimeBuilder
+    pub fn with_text_body(&mut self, text: impl Into<String>) -> &mut EmailMimeBuilder {
+        self.text_body = Some(text.into());
+        self
+    }
+
+    /// Set text/html body
+    ///
+    /// Returns &mut EmailMimeBuilder
+    pub fn with_html_body(&mut self, html: impl Into<String>) -> &mut EmailMimeBuilder {
+        self.html_body = Some(html.into());
+        self
+    }
+
+    /// Add attachment from in memory content, content is base64 encoded
+    ///
+    /// file_name is attachment file name
+    ///
+    /// content_type is attachment content type
+    ///
+    /// Returns &mut EmailMimeBuilder
+    pub fn with_attachment(
+        &mut self,
+        file_name: impl Into<String>,
+        content_type: impl Into<String>,
+        content: impl Into<Bytes>,
+    ) -> &mut EmailMimeBuilder {
+        let part = MimePart::new(content_type, content);
+        self.with_attachment_part(file_name, part)
+    }
+
+    /// Add attachment from file, file is read and base64 encoded on build
+    ///
+    /// path is file path
+    ///
+    /// content_type is attachment content type
+    ///
+    /// file_name is attachment file name
+    ///
+    /// Returns &mut EmailMimeBuilder
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    pub fn with_file_attachment(
+        &mut self,
+        path: impl AsRef<Path>,
+        content_type: impl Into<String>,
+        file_name: impl Into<String>,
+    ) -> &mut EmailMimeBuilder {
+        let part = MimePart::file(path, content_type);
+        self.with_attachment_part(file_name, part)
+    }
+
+    fn with_attachment_part(
+        &mut self,
+        file_name: impl Into<String>,
+        part: MimePart,
+    ) -> &mut EmailMimeBuilder {
+        self.attachments.push(part.with_header(
+            "Content-Disposition",
+            format!("attachment; filename=\"{}\"", file_name.into()),
+        ));
+        self
+    }
+
+    /// Build message
+    ///
+    /// Returns message bytes, headers followed by MIME-Version and body
+    pub fn build(&self) -> Vec<u8> {
+        let headers = self
+            .headers
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()));
+        let body = match (&self.text_body, &self.html_body) {
+            (Some(text), Some(html)) => {
+                let ((_, content_type), body) = MultipartAlternativeBuilder::new()
+                    .with_plain_text(text.clone())
+                    .with_html(html.clone())
+                    .build();
+                MimePart::new(content_type, body)
+            }
+            (None, Some(html)) => MimePart::new("text/html; charset=utf-8", html.clone()),
+            (text, None) => MimePart::new(
+                "text/plain; charset=utf-8",
+                text.clone().unwrap_or_default(),
+            ),
+        };
+        if self.attachments.is_empty() {
+            return body.to_message(headers);
+        }
+        let boundary = random_boundary();
+        let mut parts = vec![body];
+        parts.extend(self.attachments.iter().cloned().map(MimePart::base64));
+        MimePart::new(
+            format!("multipart/mixed; boundary=\"{}\"", boundary),
+            write_multipart(&boundary, &parts),
+        )
+        .to_message(headers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MultiPartFormDataParser;
+
+    #[test]
+    fn test_should_build_message_with_attachment() {
+        let mut email_mime_builder = EmailMimeBuilder::new();
+        email_mime_builder
+            .with_header("From", "sender@example.com")
+            .with_header("Subject", "Report")
+            .with_text_body("See attached")
+            .with_html_body("<p>See attached</p>")
+            .with_attachment("report.bin", "application/octet-stream", vec![0, 1, 2]);
+        let message = email_mime_builder.build();
+
+        let separator = message
+            .windows(4)
+            .position(|window| window == b"\r\n\r\n")
+            .unwrap();
+        let head = String::from_utf8(message[..separator].to_vec()).unwrap();
+        assert!(head
+            .starts_with("From: sender@example.com\r\nSubject: Report\r\nMIME-Version: 1.0\r\n"));
+        let content_type = head
+            .lines()
+            .last()
+            .unwrap()
+            .strip_prefix("Content-Type: ")
+            .unwrap();
+
+        let parsed =
+            MultiPartFormDataParser::parse(content_type, &message[separator + 4..]).unwrap();
+        assert_eq!(parsed.parts.len(), 2);
+        let alternative = &parsed.parts[0];
+        assert!(alternative
+            .content_type
+            .as_deref()
+            .unwrap()
+            .starts_with("multipart/alternative"));
+        parsed.parts[1]
+            .assert_header("Content-Disposition", "attachment; filename=\"report.bin\"")
+            .assert_header("Content-Transfer-Encoding", "base64")
+            .assert_text("AAEC");
+    }
+}
@@ -0,0 +1,457 @@
+use std::io::Write;
+
+use flate2::{write::GzEncoder, Compression};
+
+use crate::{budget::BuildError, part::Part};
+
+/// Encoding applied to the whole multipart/form-data body
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BodyEncoding {
+    /// Body is sent as is, no Content-Encoding header
+    #[default]
+    Identity,
+    /// Body is gzip compressed, Content-Encoding is "gzip"
+    Gzip,
+    /// Body is brotli compressed, Content-Encoding is "br", requires "brotli" feature
+    #[cfg(feature = "brotli")]
+    Brotli,
+    /// Body is zstd compressed, Content-Encoding is "zstd", requires "zstd" feature
+    #[cfg(feature = "zstd")]
+    Zstd,
+}
+
+impl BodyEncoding {
+    /// Content-Encoding header value
+    ///
+    /// Returns None for Identity
+    pub fn header_value(&self) -> Option<&'static str> {
+        match self {
+            BodyEncoding::Identity => None,
+            BodyEncoding::Gzip => Some("gzip"),
+            #[cfg(feature = "brotli")]
+            BodyEncoding::Brotli => Some("br"),
+            #[cfg(feature = "zstd")]
+            BodyEncoding::Zstd => Some("zstd"),
+        }
+    }
+
+    /// Encode body
+    pub fn encode(&self, body: Vec<u8>) -> Vec<u8> {
+        match self {
+            BodyEncoding::Identity => body,
+            BodyEncoding::Gzip => gzip(&body),
+            #[cfg(feature = "brotli")]
+            BodyEncoding::Brotli => {
+                let mut encoded = vec![];
+                let params = brotli::enc::BrotliEncoderParams::default();
+                brotli::BrotliCompress(&mut body.as_slice(), &mut encoded, &params).unwrap();
+                encoded
+            }
+            #[cfg(feature = "zstd")]
+            BodyEncoding::Zstd => zstd::encode_all(body.as_slice(), 0).unwrap(),
+        }
+    }
+}
+
+/// Encoding of field names in Content-Disposition
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FieldNameEncoding {
+    /// Names are sent as raw UTF-8, like browsers do
+    #[default]
+    Raw,
+    /// Non ASCII bytes, control characters, '"' and '%' are percent encoded, e.g. "café" is
+    /// "caf%C3%A9", per the older interpretation of RFC 7578
+    PercentEncoded,
+}
+
+impl FieldNameEncoding {
+    /// Encode field name
+    pub fn encode(&self, name: &[u8]) -> Vec<u8> {
+        match self {
+            FieldNameEncoding::Raw => name.to_vec(),
+            FieldNameEncoding::PercentEncoded => {
+                let mut encoded = Vec::with_capacity(name.len());
+                for &byte in name.iter() {
+                    if byte >= 0x80 || byte.is_ascii_control() || byte == b'"' || byte == b'%' {
+                        encoded.extend(format!("%{:02X}", byte).as_bytes());
+                    } else {
+                        encoded.push(byte);
+                    }
+                }
+                encoded
+            }
+        }
+    }
+}
+
+/// Policy for field names with special characters, i.e. space, ';', '=', '"' and '\\'
+///
+/// Applied after FieldNameEncoding, to the name as written
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SpecialCharacterPolicy {
+    /// Names are kept inside the quoted-string, '"' and '\\' are escaped with '\\'
+    #[default]
+    Quote,
+    /// Special characters are percent encoded, e.g. "meta data[x]" is "meta%20data[x]"
+    PercentEncode,
+    /// try_build returns BuildError::SpecialCharacters when a name has special characters, every
+    /// other build panics
+    Reject,
+}
+
+impl SpecialCharacterPolicy {
+    /// Apply policy to field name
+    ///
+    /// Reject keeps name unchanged, names are checked on build
+    pub fn apply(&self, name: &[u8]) -> Vec<u8> {
+        match self {
+            SpecialCharacterPolicy::Quote => name.to_vec(),
+            SpecialCharacterPolicy::PercentEncode => {
+                let mut encoded = Vec::with_capacity(name.len());
+                for byte in name.iter() {
+                    if is_special_character(byte) {
+                        encoded.extend(format!("%{:02X}", byte).as_bytes());
+                    } else {
+                        encoded.push(*byte);
+                    }
+                }
+                encoded
+            }
+            SpecialCharacterPolicy::Reject => name.to_vec(),
+        }
+    }
+}
+
+fn is_special_character(byte: &u8) -> bool {
+    matches!(byte, b' ' | b';' | b'=' | b'"' | b'\\')
+}
+
+/// Check field names as written against special character policy
+pub(crate) fn check_special_characters(
+    parts: &[Part],
+    field_name_encoding: FieldNameEncoding,
+    special_character_policy: SpecialCharacterPolicy,
+) -> Result<(), BuildError> {
+    if special_character_policy != SpecialCharacterPolicy::Reject {
+        return Ok(());
+    }
+    match parts.iter().find(|part| {
+        field_name_encoding
+            .encode(&part.name)
+            .iter()
+            .any(is_special_character)
+    }) {
+        Some(part) => Err(BuildError::SpecialCharacters {
+            part: String::from_utf8_lossy(&part.name).into_owned(),
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Policy for file names with control characters, e.g. NUL, CR or LF
+///
+/// Parts created with raw_text, raw_file or Part::raw are always written as is
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ControlCharacterPolicy {
+    /// Control characters are percent encoded, e.g. "a\nb" is "a%0Ab", like browsers do
+    #[default]
+    PercentEncode,
+    /// try_build returns BuildError::ControlCharacters when a file name has control characters,
+    /// every other build panics
+    Reject,
+    /// Control characters are written as is, e.g. to test filename sanitization, NUL and CRLF
+    /// may truncate the file name or inject headers on the server
+    UnsafeVerbatim,
+}
+
+impl ControlCharacterPolicy {
+    /// Apply policy to file name
+    ///
+    /// Reject keeps file name unchanged, file names are checked on build
+    pub fn apply(&self, file_name: &[u8]) -> Vec<u8> {
+        match self {
+            ControlCharacterPolicy::PercentEncode => {
+                let mut encoded = Vec::with_capacity(file_name.len());
+                for &byte in file_name.iter() {
+                    if byte.is_ascii_control() {
+                        encoded.extend(format!("%{:02X}", byte).as_bytes());
+                    } else {
+                        encoded.push(byte);
+                    }
+                }
+                encoded
+            }
+            ControlCharacterPolicy::Reject | ControlCharacterPolicy::UnsafeVerbatim => {
+                file_name.to_vec()
+            }
+        }
+    }
+}
+
+/// Check file names of parts that are not raw against control character policy
+pub(crate) fn check_control_characters(
+    parts: &[Part],
+    control_character_policy: ControlCharacterPolicy,
+) -> Result<(), BuildError> {
+    if control_character_policy != ControlCharacterPolicy::Reject {
+        return Ok(());
+    }
+    for part in parts.iter().filter(|part| !part.raw) {
+        if let Some(file_name) = part
+            .file_name
+            .as_deref()
+            .filter(|file_name| has_control_characters(file_name))
+        {
+            return Err(BuildError::ControlCharacters {
+                part: String::from_utf8_lossy(&part.name).into_owned(),
+                file_name: String::from_utf8_lossy(file_name).into_owned(),
+            });
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn has_control_characters(value: &[u8]) -> bool {
+    value.iter().any(u8::is_ascii_control)
+}
+
+/// Unicode normalization of file names, requires "unicode-normalization" feature
+#[cfg(feature = "unicode-normalization")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FileNameNormalization {
+    /// File names are sent as given
+    #[default]
+    Unchanged,
+    /// Composed form, e.g. "é" is one code point, as most clients send
+    Nfc,
+    /// Decomposed form, e.g. "é" is "e" and a combining accent, as macOS clients send
+    Nfd,
+}
+
+#[cfg(feature = "unicode-normalization")]
+impl FileNameNormalization {
+    /// Normalize file name, names that are not UTF-8 are unchanged
+    pub fn normalize(&self, file_name: &[u8]) -> Vec<u8> {
+        use unicode_normalization::UnicodeNormalization;
+
+        match (self, std::str::from_utf8(file_name)) {
+            (FileNameNormalization::Nfc, Ok(file_name)) => {
+                file_name.nfc().collect::<String>().into()
+            }
+            (FileNameNormalization::Nfd, Ok(file_name)) => {
+                file_name.nfd().collect::<String>().into()
+            }
+            _ => file_name.to_vec(),
+        }
+    }
+}
+
+/// Replace "\r" and "\n" not part of a "\r\n" pair with "\r\n"
+pub(crate) fn normalize_newlines(value: &[u8]) -> Vec<u8> {
+    let mut normalized = Vec::with_capacity(value.len());
+    let mut bytes = value.iter().peekable();
+    while let Some(&byte) = bytes.next() {
+        match byte {
+            b'\r' => {
+                bytes.next_if_eq(&&b'\n');
+                normalized.extend_from_slice(b"\r\n");
+            }
+            b'\n' => normalized.extend_from_slice(b"\r\n"),
+            _ => normalized.push(byte),
+        }
+    }
+    normalized
+}
+
+pub(crate) fn gzip(data: &[u8]) -> Vec<u8> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(data).unwrap();
+    encoder.finish().unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use flate2::read::GzDecoder;
+
+    use super::*;
+    use crate::MultiPartFormDataBuilder;
+
+    #[test]
+    fn test_should_gzip_body_with_content_encoding() {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder
+            .with_text("name", "some_name")
+            .with_body_encoding(BodyEncoding::Gzip);
+        let form_data = multipart_form_data_builder.build_form_data();
+
+        assert_eq!(form_data.header("Content-Encoding"), Some("gzip"));
+        assert_eq!(
+            form_data.header("Content-Length").unwrap(),
+            form_data.body.len().to_string()
+        );
+
+        let mut decoded = String::new();
+        GzDecoder::new(form_data.body.as_slice())
+            .read_to_string(&mut decoded)
+            .unwrap();
+        assert!(decoded.contains("some_name"));
+    }
+
+    #[cfg(feature = "brotli")]
+    #[test]
+    fn test_should_brotli_body_with_content_encoding() {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder
+            .with_text("name", "some_name")
+            .with_boundary("boundary")
+            .with_body_encoding(BodyEncoding::Brotli);
+        let (_, body) = multipart_form_data_builder.build();
+        let form_data = multipart_form_data_builder.build_form_data();
+
+        assert_eq!(form_data.header("Content-Encoding"), Some("br"));
+        let mut decoded = vec![];
+        brotli::BrotliDecompress(&mut form_data.body.as_slice(), &mut decoded).unwrap();
+        assert_eq!(decoded, body);
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn test_should_zstd_body_with_content_encoding() {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder
+            .with_text("name", "some_name")
+            .with_boundary("boundary")
+            .with_body_encoding(BodyEncoding::Zstd);
+        let (_, body) = multipart_form_data_builder.build();
+        let form_data = multipart_form_data_builder.build_form_data();
+
+        assert_eq!(form_data.header("Content-Encoding"), Some("zstd"));
+        assert_eq!(zstd::decode_all(form_data.body.as_slice()).unwrap(), body);
+    }
+
+    #[test]
+    fn test_should_percent_encode_field_names() {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder
+            .with_text("café", "value")
+            .with_text("100%", "value")
+            .with_field_name_encoding(FieldNameEncoding::PercentEncoded);
+        let (_, body) = multipart_form_data_builder.build();
+        let body = String::from_utf8(body).unwrap();
+
+        assert!(body.contains("name=\"caf%C3%A9\""));
+        assert!(body.contains("name=\"100%25\""));
+        assert!(!body.contains("café"));
+    }
+
+    #[test]
+    fn test_should_apply_special_character_policy() {
+        let name = b"meta data[x];a=\"b\"";
+
+        assert_eq!(SpecialCharacterPolicy::Quote.apply(name), name);
+        assert_eq!(
+            SpecialCharacterPolicy::PercentEncode.apply(name),
+            b"meta%20data[x]%3Ba%3D%22b%22"
+        );
+        assert_eq!(SpecialCharacterPolicy::Reject.apply(b"name[x]"), b"name[x]");
+    }
+
+    #[test]
+    #[should_panic(expected = "has special characters")]
+    fn test_should_reject_special_characters_on_build() {
+        MultiPartFormDataBuilder::new()
+            .with_text("meta data", "value")
+            .with_special_character_policy(SpecialCharacterPolicy::Reject)
+            .build();
+    }
+
+    #[test]
+    fn test_should_return_error_on_try_build_with_special_characters() {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder
+            .with_text("name", "value")
+            .with_text("meta data", "value")
+            .with_special_character_policy(SpecialCharacterPolicy::Reject);
+
+        assert_eq!(
+            multipart_form_data_builder.try_build(),
+            Err(BuildError::SpecialCharacters {
+                part: "meta data".to_string()
+            })
+        );
+        multipart_form_data_builder
+            .with_special_character_policy(SpecialCharacterPolicy::PercentEncode);
+        assert!(multipart_form_data_builder.try_build().is_ok());
+    }
+
+    #[test]
+    fn test_should_not_add_content_encoding_for_identity() {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder.with_text("name", "some_name");
+        let form_data = multipart_form_data_builder.build_form_data();
+
+        assert_eq!(form_data.header("Content-Encoding"), None);
+    }
+
+    #[cfg(feature = "unicode-normalization")]
+    #[test]
+    fn test_should_normalize_file_names() {
+        let composed = "caf\u{e9}.png".as_bytes();
+        let decomposed = "cafe\u{301}.png".as_bytes();
+
+        assert_eq!(FileNameNormalization::Nfd.normalize(composed), decomposed);
+        assert_eq!(FileNameNormalization::Nfc.normalize(decomposed), composed);
+        assert_eq!(
+            FileNameNormalization::Unchanged.normalize(decomposed),
+            decomposed
+        );
+        assert_eq!(
+            FileNameNormalization::Nfc.normalize(b"\xff.png"),
+            b"\xff.png"
+        );
+    }
+
+    #[test]
+    fn test_should_apply_control_character_policy_to_file_names() {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder.with_bytes("file", "evil.php\0.png", "image/png", "");
+        let (_, body) = multipart_form_data_builder.build();
+        assert!(String::from_utf8(body)
+            .unwrap()
+            .contains("filename=\"evil.php%00.png\""));
+
+        multipart_form_data_builder
+            .with_control_character_policy(ControlCharacterPolicy::UnsafeVerbatim);
+        let (_, body) = multipart_form_data_builder.build();
+        assert!(String::from_utf8(body)
+            .unwrap()
+            .contains("filename=\"evil.php\0.png\""));
+    }
+
+    #[test]
+    #[should_panic(expected = "has control characters")]
+    fn test_should_reject_control_characters_on_build() {
+        MultiPartFormDataBuilder::new()
+            .with_bytes("file", "a\r\nX-Evil: 1", "image/png", "")
+            .with_control_character_policy(ControlCharacterPolicy::Reject)
+            .build();
+    }
+
+    #[test]
+    fn test_should_return_error_on_try_build_with_control_characters() {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder
+            .with_bytes("file", "evil.php\0.png", "image/png", "")
+            .with_control_character_policy(ControlCharacterPolicy::Reject);
+
+        assert_eq!(
+            multipart_form_data_builder.try_build(),
+            Err(BuildError::ControlCharacters {
+                part: "file".to_string(),
+                file_name: "evil.php\0.png".to_string()
+            })
+        );
+    }
+}
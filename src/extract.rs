@@ -0,0 +1,162 @@
+use std::fmt::Debug;
+
+use actix_http::Request;
+use actix_web::{
+    dev::{Payload, Service, ServiceResponse},
+    http::Method,
+    test::{self, TestRequest},
+    HttpRequest,
+};
+
+use crate::MultiPartFormDataBuilder;
+
+/// Build multipart/form-data, send it to app with method and return the response
+///
+/// app is service from actix_web::test::init_service
+///
+/// method is request method, e.g. Method::POST, Method::PUT or Method::PATCH
+///
+/// uri is request uri, e.g. "/upload"
+///
+/// Panics when the service returns an error
+///
+/// Requires "actix" feature
+///
+/// # Examples
+///
+/// ```
+/// use actix_multipart_test::{call_multipart, MultiPartFormDataBuilder};
+/// use actix_web::{http::Method, test, web, App, HttpResponse};
+///
+/// # actix_web::rt::System::new().block_on(async {
+/// let app = test::init_service(
+///     App::new().route("/avatar", web::put().to(|| async { HttpResponse::Ok() })),
+/// )
+/// .await;
+/// let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+/// multipart_form_data_builder.with_text("name", "some_name");
+///
+/// let resp = call_multipart(&app, Method::PUT, "/avatar", &multipart_form_data_builder).await;
+/// assert!(resp.status().is_success());
+/// # });
+/// ```
+pub async fn call_multipart<S, B, E>(
+    app: &S,
+    method: Method,
+    uri: &str,
+    multipart_form_data_builder: &MultiPartFormDataBuilder,
+) -> ServiceResponse<B>
+where
+    S: Service<Request, Response = ServiceResponse<B>, Error = E>,
+    E: Debug,
+{
+    let req = multipart_form_data_builder
+        .test_request(TestRequest::default().method(method).uri(uri))
+        .to_request();
+    test::call_service(app, req).await
+}
+
+impl MultiPartFormDataBuilder {
+    /// Build multipart/form-data into a POST TestRequest with headers of build_form_data and
+    /// body as payload
+    ///
+    /// Requires "actix" feature
+    pub fn to_test_request(&self) -> TestRequest {
+        self.test_request(TestRequest::post())
+    }
+
+    /// Add headers of build_form_data and body as payload to test_request
+    fn test_request(&self, mut test_request: TestRequest) -> TestRequest {
+        let form_data = self.build_form_data();
+        for header in form_data.headers {
+            test_request = test_request.insert_header(header);
+        }
+        test_request.set_payload(form_data.body)
+    }
+
+    /// Build multipart/form-data into request and payload for extractor unit tests, e.g.
+    /// Multipart::from_request or MultipartForm::<T>::from_request without an App
+    ///
+    /// Requires "actix" feature
+    ///
+    /// Returns (HttpRequest, Payload)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use actix_multipart_test::MultiPartFormDataBuilder;
+    /// use actix_web::{web::Bytes, FromRequest};
+    ///
+    /// # actix_web::rt::System::new().block_on(async {
+    /// let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+    /// multipart_form_data_builder.with_text("name", "some_name");
+    /// let (req, mut payload) = multipart_form_data_builder.to_http_parts();
+    ///
+    /// let body = Bytes::from_request(&req, &mut payload).await.unwrap();
+    /// # });
+    /// ```
+    pub fn to_http_parts(&self) -> (HttpRequest, Payload) {
+        self.to_test_request().to_http_parts()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{
+        http::{header::CONTENT_TYPE, StatusCode},
+        web::{self, Bytes},
+        App, FromRequest, HttpResponse,
+    };
+
+    use super::*;
+    use crate::MultiPartFormDataParser;
+
+    #[actix_web::test]
+    async fn test_should_extract_body_from_http_parts() {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder.with_text("name", "some_name");
+        let (req, mut payload) = multipart_form_data_builder.to_http_parts();
+
+        let content_type = req.headers().get(CONTENT_TYPE).unwrap().to_str().unwrap();
+        let body = Bytes::from_request(&req, &mut payload).await.unwrap();
+        let parsed = MultiPartFormDataParser::parse(content_type, &body).unwrap();
+        parsed.assert_part("name").assert_text("some_name");
+    }
+
+    #[cfg(feature = "actix-multipart")]
+    #[actix_web::test]
+    async fn test_should_extract_multipart() {
+        use actix_multipart::Multipart;
+        use futures_util::StreamExt;
+
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder.with_text("name", "some_name");
+        let (req, mut payload) = multipart_form_data_builder.to_http_parts();
+
+        let mut multipart = Multipart::from_request(&req, &mut payload).await.unwrap();
+        let field = multipart.next().await.unwrap().unwrap();
+        assert_eq!(field.name(), "name");
+    }
+
+    #[actix_web::test]
+    async fn test_should_call_multipart_with_method() {
+        let app = test::init_service(App::new().route(
+            "/upload",
+            web::patch().to(|body: Bytes| async move { HttpResponse::Ok().body(body) }),
+        ))
+        .await;
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder
+            .with_text("name", "some_name")
+            .with_boundary("boundary");
+        let (_, body) = multipart_form_data_builder.build();
+
+        let resp =
+            call_multipart(&app, Method::PATCH, "/upload", &multipart_form_data_builder).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(test::read_body(resp).await, body);
+        let resp =
+            call_multipart(&app, Method::POST, "/upload", &multipart_form_data_builder).await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    }
+}
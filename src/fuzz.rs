@@ -0,0 +1,181 @@
+use crate::{MultiPartFormDataBuilder, Part};
+
+/// Structured description of a form, e.g. decoded from fuzzer bytes in cargo-fuzz targets
+///
+/// Implements arbitrary::Arbitrary with the "arbitrary" feature, at most 15 parts like
+/// from_fuzz_bytes
+///
+/// # Examples
+///
+/// ```
+/// use actix_multipart_test::FormDescription;
+///
+/// // fuzz_target!(|data: &[u8]| { ... })
+/// let data = b"\x02\x00\x04name\x05value\x03\x04file\x05a.png\x09image/png\x03\x89PN";
+/// let (header, body) = FormDescription::from_fuzz_bytes(data).to_builder().build();
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FormDescription {
+    /// parts in written order
+    pub parts: Vec<PartDescription>,
+}
+
+/// Structured description of a part
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PartDescription {
+    /// form name
+    pub name: Vec<u8>,
+    /// file name, None for text parts
+    pub file_name: Option<Vec<u8>>,
+    /// content type, no Content-Type header when None
+    pub content_type: Option<String>,
+    /// content
+    pub content: Vec<u8>,
+    /// name and file name are written as is, e.g. near-valid parts with '"' or CRLF
+    pub raw: bool,
+}
+
+impl FormDescription {
+    /// Decode description from fuzzer bytes, every input decodes, missing bytes are zeros
+    ///
+    /// First byte is part count modulo 16, each part is a flags byte, length prefixed name,
+    /// file name when flags has 0x02, content type when flags has 0x01 and content, raw when
+    /// flags has 0x04
+    pub fn from_fuzz_bytes(data: &[u8]) -> FormDescription {
+        let mut data = FuzzBytes(data);
+        let count = data.byte() % 16;
+        let parts = (0..count)
+            .map(|_| {
+                let flags = data.byte();
+                let name = data.slice().to_vec();
+                let file_name = (flags & 0x02 != 0).then(|| data.slice().to_vec());
+                let content_type =
+                    (flags & 0x01 != 0).then(|| String::from_utf8_lossy(data.slice()).into_owned());
+                PartDescription {
+                    name,
+                    file_name,
+                    content_type,
+                    content: data.slice().to_vec(),
+                    raw: flags & 0x04 != 0,
+                }
+            })
+            .collect();
+        FormDescription { parts }
+    }
+
+    /// Builder with described parts
+    pub fn to_builder(&self) -> MultiPartFormDataBuilder {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        for part in self.parts.iter() {
+            multipart_form_data_builder.with_part(part.to_part());
+        }
+        multipart_form_data_builder
+    }
+}
+
+impl PartDescription {
+    /// Part with described name, file name, content type and content
+    pub fn to_part(&self) -> Part {
+        let mut part = Part::raw_text(self.name.clone(), self.content.clone());
+        part.file_name = self.file_name.clone();
+        part.content_type = self.content_type.clone();
+        part.raw = self.raw;
+        part
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for FormDescription {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<FormDescription> {
+        let count = u.arbitrary_len::<PartDescription>()?.min(15);
+        let parts = (0..count)
+            .map(|_| u.arbitrary())
+            .collect::<arbitrary::Result<_>>()?;
+        Ok(FormDescription { parts })
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for PartDescription {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<PartDescription> {
+        Ok(PartDescription {
+            name: u.arbitrary()?,
+            file_name: u.arbitrary()?,
+            content_type: u.arbitrary()?,
+            content: u.arbitrary()?,
+            raw: u.arbitrary()?,
+        })
+    }
+}
+
+/// Fuzzer bytes consumed front to back
+struct FuzzBytes<'a>(&'a [u8]);
+
+impl<'a> FuzzBytes<'a> {
+    fn byte(&mut self) -> u8 {
+        match self.0.split_first() {
+            Some((byte, rest)) => {
+                self.0 = rest;
+                *byte
+            }
+            None => 0,
+        }
+    }
+
+    /// Slice prefixed with its length byte, shorter when data runs out
+    fn slice(&mut self) -> &'a [u8] {
+        let len = (self.byte() as usize).min(self.0.len());
+        let (slice, rest) = self.0.split_at(len);
+        self.0 = rest;
+        slice
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MultiPartFormDataParser;
+
+    #[test]
+    fn test_should_decode_parts_from_fuzz_bytes() {
+        let data = b"\x02\x00\x04name\x05value\x03\x04file\x05a.png\x09image/png\x03\x89PN";
+        let form_description = FormDescription::from_fuzz_bytes(data);
+
+        assert_eq!(form_description.parts.len(), 2);
+        assert_eq!(
+            form_description.parts[1].file_name.as_deref(),
+            Some(&b"a.png"[..])
+        );
+        assert!(!form_description.parts[1].raw);
+        let ((_, content_type), body) = form_description.to_builder().build();
+        let parsed = MultiPartFormDataParser::parse(&content_type, &body).unwrap();
+        parsed.assert_part("name").assert_text("value");
+        parsed
+            .assert_part("file")
+            .assert_file_name("a.png")
+            .assert_content_type("image/png")
+            .assert_data(b"\x89PN");
+    }
+
+    #[test]
+    fn test_should_decode_any_bytes() {
+        for data in [&b""[..], b"\xff", b"\x0f\x07\xff", b"\x03\x06\x01\"\x01\r"] {
+            let (_, body) = FormDescription::from_fuzz_bytes(data).to_builder().build();
+            assert!(body.ends_with(b"--\r\n"));
+        }
+    }
+
+    #[cfg(feature = "arbitrary")]
+    #[test]
+    fn test_should_build_arbitrary_forms() {
+        use arbitrary::{Arbitrary, Unstructured};
+
+        for data in [&b""[..], b"\xff\x00\x13", &[0x5a; 512]] {
+            let form_description =
+                FormDescription::arbitrary(&mut Unstructured::new(data)).unwrap();
+            assert!(form_description.parts.len() <= 15);
+            let (_, body) = form_description.to_builder().build();
+            assert!(body.ends_with(b"--\r\n"));
+        }
+    }
+}
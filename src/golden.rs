@@ -0,0 +1,96 @@
+use crate::MultiPartFormDataBuilder;
+
+impl MultiPartFormDataBuilder {
+    /// Enable golden mode with a fixed boundary, so the same parts build the same body byte for
+    /// byte in every crate version, e.g. for golden files
+    ///
+    /// boundary is used by every build, build panics when it is later cleared with
+    /// with_boundary_generator
+    ///
+    /// The layout of a golden body is, with CRLF line endings:
+    ///
+    /// ```text
+    /// --{boundary}
+    /// Content-Disposition: form-data; name="{name}"; filename="{file_name}"
+    /// Content-Type: {content_type}
+    /// {header}: {value}
+    /// Content-Length: {content length}
+    ///
+    /// {content}
+    /// --{boundary}--
+    /// ```
+    ///
+    /// One section per part, filename only for file parts, Content-Type only when the part has
+    /// one, a line per header added with Part::with_header. Changing this layout or the defaults
+    /// it depends on, e.g. escaping of '"' and '\\' in names or the control character policy, is
+    /// a breaking change
+    ///
+    /// Returns &mut MultiPartFormDataBuilder
+    pub fn with_golden_mode(
+        &mut self,
+        boundary: impl Into<String>,
+    ) -> &mut MultiPartFormDataBuilder {
+        self.golden = true;
+        self.with_boundary(boundary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Part;
+
+    #[test]
+    fn test_should_build_golden_body() {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder
+            .with_text("name", "some_name")
+            .with_bytes("file", "a \"b\".txt", "text/plain", "abc")
+            .with_part(
+                Part::bytes("raw", "raw.bin", "application/octet-stream", "")
+                    .without_content_type(),
+            )
+            .with_golden_mode("golden");
+        let ((_, content_type), body) = multipart_form_data_builder.build();
+
+        assert_eq!(content_type, "multipart/form-data; boundary=golden");
+        assert_eq!(
+            String::from_utf8(body).unwrap(),
+            concat!(
+                "--golden\r\n",
+                "Content-Disposition: form-data; name=\"name\"\r\n",
+                "Content-Type: text/plain\r\n",
+                "Content-Length: 9\r\n",
+                "\r\n",
+                "some_name\r\n",
+                "--golden\r\n",
+                "Content-Disposition: form-data; name=\"file\"; filename=\"a \\\"b\\\".txt\"\r\n",
+                "Content-Type: text/plain\r\n",
+                "Content-Length: 3\r\n",
+                "\r\n",
+                "abc\r\n",
+                "--golden\r\n",
+                "Content-Disposition: form-data; name=\"raw\"; filename=\"raw.bin\"\r\n",
+                "Content-Length: 0\r\n",
+                "\r\n",
+                "\r\n",
+                "--golden--\r\n",
+            )
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "golden mode needs a fixed boundary")]
+    fn test_should_panic_when_golden_boundary_is_cleared() {
+        MultiPartFormDataBuilder::new()
+            .with_golden_mode("golden")
+            .with_boundary_generator(|| "generated".to_string())
+            .build();
+    }
+
+    #[test]
+    #[should_panic(expected = "boundary is empty")]
+    fn test_should_panic_on_invalid_golden_boundary() {
+        MultiPartFormDataBuilder::new().with_golden_mode("").build();
+    }
+}
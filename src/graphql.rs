@@ -0,0 +1,185 @@
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+use std::path::Path;
+
+use bytes::Bytes;
+
+use crate::{MultiPartFormDataBuilder, Part};
+
+/// Simple builder for GraphQL multipart requests, see
+/// https://github.com/jaydenseric/graphql-multipart-request-spec
+///
+/// Writes an "operations" part, a "map" part and one file part per mapped file, file parts are
+/// named "0", "1", ... in insertion order
+///
+/// # Examples
+///
+/// ```
+/// use actix_multipart_test::GraphqlMultipartBuilder;
+///
+/// let mut graphql_multipart_builder = GraphqlMultipartBuilder::new(
+///     r#"{"query":"mutation ($file: Upload!) { upload(file: $file) }","variables":{"file":null}}"#,
+/// );
+/// graphql_multipart_builder.with_file("variables.file", "tests/sample.png", "image/png", "sample.png");
+/// let (header, body) = graphql_multipart_builder.build();
+/// ```
+pub struct GraphqlMultipartBuilder {
+    operations: String,
+    files: Vec<(Vec<String>, Part)>,
+}
+
+impl GraphqlMultipartBuilder {
+    /// Create new GraphqlMultipartBuilder
+    ///
+    /// operations is operations JSON, file variables are null
+    pub fn new(operations: impl Into<String>) -> GraphqlMultipartBuilder {
+        GraphqlMultipartBuilder {
+            operations: operations.into(),
+            files: vec![],
+        }
+    }
+
+    /// Add file mapped to an operations path
+    ///
+    /// variable is object path in operations, e.g. "variables.file" or "0.variables.files.1"
+    ///
+    /// path is file path
+    ///
+    /// content_type is file content type
+    ///
+    /// file_name is file name
+    ///
+    /// Returns &mut GraphqlMultipartBuilder
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    pub fn with_file(
+        &mut self,
+        variable: impl Into<String>,
+        path: impl AsRef<Path>,
+        content_type: impl Into<String>,
+        file_name: impl Into<String>,
+    ) -> &mut GraphqlMultipartBuilder {
+        let part = Part::file(path, "", content_type, file_name);
+        self.with_mapped_part([variable], part)
+    }
+
+    /// Add file from in memory content mapped to an operations path
+    ///
+    /// variable is object path in operations, e.g. "variables.file"
+    ///
+    /// Returns &mut GraphqlMultipartBuilder
+    pub fn with_bytes(
+        &mut self,
+        variable: impl Into<String>,
+        file_name: impl Into<String>,
+        content_type: impl Into<String>,
+        content: impl Into<Bytes>,
+    ) -> &mut GraphqlMultipartBuilder {
+        let part = Part::bytes("", file_name, content_type, content);
+        self.with_mapped_part([variable], part)
+    }
+
+    /// Add part mapped to several operations paths, e.g. one file used by two variables
+    ///
+    /// part name is replaced by its map key
+    ///
+    /// Returns &mut GraphqlMultipartBuilder
+    pub fn with_mapped_part(
+        &mut self,
+        variables: impl IntoIterator<Item = impl Into<String>>,
+        part: Part,
+    ) -> &mut GraphqlMultipartBuilder {
+        self.files
+            .push((variables.into_iter().map(Into::into).collect(), part));
+        self
+    }
+
+    /// Create MultiPartFormDataBuilder with operations, map and file parts
+    pub fn to_builder(&self) -> MultiPartFormDataBuilder {
+        let mut map = String::from("{");
+        for (key, (variables, _)) in self.files.iter().enumerate() {
+            if key > 0 {
+                map.push(',');
+            }
+            let variables: Vec<String> = variables
+                .iter()
+                .map(|variable| json_string(variable))
+                .collect();
+            map.push_str(&format!("\"{}\":[{}]", key, variables.join(",")));
+        }
+        map.push('}');
+
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder
+            .with_part(
+                Part::text("operations", self.operations.clone())
+                    .with_content_type("application/json"),
+            )
+            .with_part(Part::text("map", map).with_content_type("application/json"));
+        for (key, (_, part)) in self.files.iter().enumerate() {
+            multipart_form_data_builder.with_part(Part {
+                name: key.to_string().into_bytes(),
+                ..part.clone()
+            });
+        }
+        multipart_form_data_builder
+    }
+
+    /// Build GraphQL multipart request
+    ///
+    /// Returns ((header_name, header_value), body)
+    ///
+    /// header_name is "Content-Type"
+    ///
+    /// header_value is "multipart/form-data; boundary=..."
+    ///
+    /// body is binary data
+    pub fn build(&self) -> ((String, String), Vec<u8>) {
+        self.to_builder().build()
+    }
+}
+
+fn json_string(value: &str) -> String {
+    let mut json = String::from("\"");
+    for char in value.chars() {
+        match char {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            char if char.is_control() => json.push_str(&format!("\\u{:04x}", char as u32)),
+            char => json.push(char),
+        }
+    }
+    json.push('"');
+    json
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MultiPartFormDataParser;
+
+    #[test]
+    fn test_should_build_operations_map_and_files() {
+        let mut graphql_multipart_builder =
+            GraphqlMultipartBuilder::new(r#"{"query":"mutation","variables":{"a":null,"b":null}}"#);
+        graphql_multipart_builder
+            .with_file("variables.a", "tests/sample.png", "image/png", "sample.png")
+            .with_mapped_part(
+                ["variables.b", "variables.c"],
+                Part::bytes("", "b.txt", "text/plain", "b"),
+            );
+        let ((_, content_type), body) = graphql_multipart_builder.build();
+
+        let parsed = MultiPartFormDataParser::parse(&content_type, &body).unwrap();
+        let names: Vec<_> = parsed
+            .parts
+            .iter()
+            .map(|part| part.name.as_deref().unwrap())
+            .collect();
+        assert_eq!(names, ["operations", "map", "0", "1"]);
+        parsed.assert_field(
+            "map",
+            r#"{"0":["variables.a"],"1":["variables.b","variables.c"]}"#,
+        );
+        parsed.assert_part("0").assert_file_name("sample.png");
+        parsed.assert_part("1").assert_text("b");
+    }
+}
@@ -0,0 +1,281 @@
+use crate::{
+    part::{Part, PartSource},
+    MultiPartFormData, MultiPartFormDataBuilder,
+};
+
+impl MultiPartFormDataBuilder {
+    /// Build complete HTTP/1.1 request, e.g. to write it directly into a TCP socket
+    ///
+    /// Contents are written as is, including sensitive parts, use Display or to_curl_command for
+    /// logs
+    ///
+    /// method is request method, e.g. "POST"
+    ///
+    /// path is request target, e.g. "/upload"
+    ///
+    /// extra_headers are written before the form and request headers, "Host: localhost" is
+    /// added when missing
+    ///
+    /// Returns request line, headers with Content-Type and Content-Length, blank line and body
+    pub fn to_raw_http_request(
+        &self,
+        method: &str,
+        path: &str,
+        extra_headers: &[(&str, &str)],
+    ) -> Vec<u8> {
+        let (mut request, body) =
+            raw_http_request_parts(self.build_form_data(), method, path, extra_headers);
+        request.extend(body);
+        request
+    }
+
+    /// Build complete HTTP/1.1 request with chunked body, see build_chunked
+    ///
+    /// method, path and extra_headers are as in to_raw_http_request
+    ///
+    /// chunk_size is maximum number of body bytes per chunk
+    ///
+    /// Returns request line, headers with Content-Type and Transfer-Encoding, blank line and
+    /// chunked body
+    pub fn to_raw_chunked_http_request(
+        &self,
+        method: &str,
+        path: &str,
+        extra_headers: &[(&str, &str)],
+        chunk_size: usize,
+    ) -> Vec<u8> {
+        let (mut request, body) =
+            raw_http_request_parts(self.build_chunked(chunk_size), method, path, extra_headers);
+        request.extend(body);
+        request
+    }
+
+    /// Build HTTP/1.1 request with "Expect: 100-continue", head and body are returned separately
+    /// to be sent in two phases, e.g. with RawTcpSender::send_expect_continue
+    ///
+    /// method, path and extra_headers are as in to_raw_http_request
+    ///
+    /// Returns (head, body), head is request line, headers and blank line
+    pub fn to_expect_continue_request(
+        &self,
+        method: &str,
+        path: &str,
+        extra_headers: &[(&str, &str)],
+    ) -> (Vec<u8>, Vec<u8>) {
+        let mut headers = extra_headers.to_vec();
+        headers.push(("Expect", "100-continue"));
+        raw_http_request_parts(self.build_form_data(), method, path, &headers)
+    }
+}
+
+impl MultiPartFormDataBuilder {
+    /// Build a curl command posting the form to url, e.g. to reproduce a failing upload by hand
+    ///
+    /// File parts read from disk are referenced by path, text parts are passed with
+    /// --form-string, sensitive text as "[redacted N bytes]"
+    ///
+    /// Contents of other file parts, e.g. Part::bytes or Part::lazy, are not exported, they are
+    /// sent as the placeholder "[content N bytes]", replace it with @path of a file holding the
+    /// content to send it, paths and file names are quoted so ';', ',' or '"' are kept
+    ///
+    /// Returns command on one line with arguments quoted for POSIX shells
+    pub fn to_curl_command(&self, url: &str) -> String {
+        let mut command = vec!["curl".to_string()];
+        for (name, value) in self.request_headers.iter() {
+            command.push("--header".to_string());
+            command.push(shell_quote(&format!("{}: {}", name, value)));
+        }
+        for part in self.parts(self.render_boundary()).iter() {
+            let name = String::from_utf8_lossy(&part.name);
+            let (option, value) = match &part.file_name {
+                None if part.sensitive => (
+                    "--form-string",
+                    format!("{}={}", name, placeholder("redacted", part)),
+                ),
+                None => {
+                    let mut content = vec![];
+                    part.read_into(&mut content, self.file_cache.as_ref());
+                    (
+                        "--form-string",
+                        format!("{}={}", name, String::from_utf8_lossy(&content)),
+                    )
+                }
+                Some(file_name) => {
+                    let mut value = match &part.source {
+                        PartSource::File(path) => {
+                            format!("{}=@{}", name, curl_quote(&path.to_string_lossy()))
+                        }
+                        _ => format!("{}={}", name, placeholder("content", part)),
+                    };
+                    if let Some(content_type) = &part.content_type {
+                        value.push_str(&format!(";type={}", content_type));
+                    }
+                    value.push_str(&format!(
+                        ";filename={}",
+                        curl_quote(&String::from_utf8_lossy(file_name))
+                    ));
+                    ("--form", value)
+                }
+            };
+            command.push(option.to_string());
+            command.push(shell_quote(&value));
+        }
+        command.push(shell_quote(url));
+        command.join(" ")
+    }
+}
+
+/// Placeholder of content that is not exported, length only when known without reading it
+fn placeholder(kind: &str, part: &Part) -> String {
+    match part.known_len() {
+        Some(len) => format!("[{} {} bytes]", kind, len),
+        None => format!("[{}]", kind),
+    }
+}
+
+/// Quote path or file name for curl --form, '"' and '\\' are escaped with '\\'
+fn curl_quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Quote argument for POSIX shells
+fn shell_quote(argument: &str) -> String {
+    format!("'{}'", argument.replace('\'', "'\\''"))
+}
+
+/// Returns (head, body) of request sending form_data
+fn raw_http_request_parts(
+    form_data: MultiPartFormData,
+    method: &str,
+    path: &str,
+    extra_headers: &[(&str, &str)],
+) -> (Vec<u8>, Vec<u8>) {
+    let mut head = format!("{} {} HTTP/1.1\r\n", method, path).into_bytes();
+    let has_host = extra_headers
+        .iter()
+        .map(|(name, _)| *name)
+        .chain(form_data.headers.iter().map(|(name, _)| name.as_str()))
+        .any(|name| name.eq_ignore_ascii_case("Host"));
+    if !has_host {
+        head.extend(b"Host: localhost\r\n");
+    }
+    let headers = extra_headers
+        .iter()
+        .map(|(name, value)| (*name, *value))
+        .chain(
+            form_data
+                .headers
+                .iter()
+                .map(|(name, value)| (name.as_str(), value.as_str())),
+        );
+    for (name, value) in headers {
+        head.extend(format!("{}: {}\r\n", name, value).as_bytes());
+    }
+    head.extend(b"\r\n");
+    (head, form_data.body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_should_export_raw_http_request() {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder
+            .with_text("name", "some_name")
+            .with_boundary("boundary");
+        let (_, body) = multipart_form_data_builder.build();
+
+        let request = multipart_form_data_builder.to_raw_http_request(
+            "POST",
+            "/upload",
+            &[("Authorization", "Bearer token")],
+        );
+
+        let mut expected = format!(
+            "POST /upload HTTP/1.1\r\n\
+             Host: localhost\r\n\
+             Authorization: Bearer token\r\n\
+             Content-Type: multipart/form-data; boundary=boundary\r\n\
+             Content-Length: {}\r\n\
+             \r\n",
+            body.len()
+        )
+        .into_bytes();
+        expected.extend(body);
+        assert_eq!(request, expected);
+    }
+
+    #[test]
+    fn test_should_split_expect_continue_request() {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder.with_text("name", "some_name");
+
+        let (head, body) =
+            multipart_form_data_builder.to_expect_continue_request("POST", "/upload", &[]);
+
+        assert!(head
+            .starts_with(b"POST /upload HTTP/1.1\r\nHost: localhost\r\nExpect: 100-continue\r\n"));
+        assert!(head.ends_with(format!("Content-Length: {}\r\n\r\n", body.len()).as_bytes()));
+        assert!(body.starts_with(b"--"));
+    }
+
+    #[test]
+    fn test_should_export_raw_chunked_http_request() {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder.with_text("name", "some_name");
+
+        let request =
+            multipart_form_data_builder.to_raw_chunked_http_request("POST", "/upload", &[], 64);
+
+        let request = String::from_utf8(request).unwrap();
+        assert!(request.contains("\r\nTransfer-Encoding: chunked\r\n\r\n40\r\n--"));
+        let head = &request[..request.find("\r\n\r\n").unwrap()];
+        assert!(!head.contains("Content-Length"));
+        assert!(request.ends_with("\r\n0\r\n\r\n"));
+    }
+
+    #[test]
+    fn test_should_export_curl_command_with_redacted_sensitive_parts() {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder
+            .with_file("tests/sample.png", "sample", "image/png", "sample.png")
+            .with_text("name", "it's")
+            .with_part(Part::text("token", "secret").sensitive())
+            .with_request_header("Authorization", "Bearer token");
+
+        let command = multipart_form_data_builder.to_curl_command("http://localhost/upload");
+
+        assert_eq!(
+            command,
+            "curl --header 'Authorization: Bearer token' \
+             --form 'sample=@\"tests/sample.png\";type=image/png;filename=\"sample.png\"' \
+             --form-string 'name=it'\\''s' \
+             --form-string 'token=[redacted 6 bytes]' \
+             'http://localhost/upload'"
+        );
+    }
+
+    #[test]
+    fn test_should_export_placeholders_for_in_memory_files_in_curl_command() {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder
+            .with_bytes("doc", "a;b,\"c\".txt", "text/plain", "content")
+            .with_part(Part::lazy(
+                "lazy",
+                "lazy.bin",
+                "application/octet-stream",
+                || b"lazy".to_vec(),
+            ));
+
+        let command = multipart_form_data_builder.to_curl_command("http://localhost/upload");
+
+        assert_eq!(
+            command,
+            "curl --form 'doc=[content 7 bytes];type=text/plain;filename=\"a;b,\\\"c\\\".txt\"' \
+             --form 'lazy=[content];type=application/octet-stream;filename=\"lazy.bin\"' \
+             'http://localhost/upload'"
+        );
+    }
+}
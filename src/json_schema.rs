@@ -0,0 +1,472 @@
+use serde_json::Value;
+
+use crate::{MultiPartFormDataBuilder, Part};
+
+impl MultiPartFormDataBuilder {
+    /// Add JSON part validated against a JSON Schema, content type is "application/json"
+    ///
+    /// name is form name
+    ///
+    /// value is JSON value
+    ///
+    /// schema is JSON Schema, keywords type, enum, const, required, properties,
+    /// additionalProperties, minProperties, maxProperties, items, minItems, maxItems, uniqueItems,
+    /// minLength, maxLength, minimum, maximum, exclusiveMinimum, exclusiveMaximum, allOf, anyOf,
+    /// oneOf, not and local $ref are checked, annotations like title or description are ignored
+    ///
+    /// Panics when value does not conform to schema, listing every error, or when schema uses
+    /// other keywords or forms, e.g. pattern, format, array form items or boolean
+    /// exclusiveMinimum, so values are never accepted unchecked
+    ///
+    /// Requires "json-schema" feature
+    ///
+    /// Returns &mut MultiPartFormDataBuilder
+    pub fn with_json_validated(
+        &mut self,
+        name: impl Into<String>,
+        value: &Value,
+        schema: &Value,
+    ) -> &mut MultiPartFormDataBuilder {
+        let name = name.into();
+        check_keywords(schema);
+        let mut errors = vec![];
+        validate(value, schema, schema, "", &mut errors);
+        assert!(
+            errors.is_empty(),
+            "value of `{}` does not conform to schema: {}",
+            name,
+            errors.join(", ")
+        );
+        self.with_part(Part::text(name, value.to_string()).with_content_type("application/json"))
+    }
+}
+
+/// Keywords checked by validate
+const KEYWORDS: &[&str] = &[
+    "type",
+    "enum",
+    "const",
+    "required",
+    "properties",
+    "additionalProperties",
+    "minProperties",
+    "maxProperties",
+    "items",
+    "minItems",
+    "maxItems",
+    "uniqueItems",
+    "minLength",
+    "maxLength",
+    "minimum",
+    "maximum",
+    "exclusiveMinimum",
+    "exclusiveMaximum",
+    "allOf",
+    "anyOf",
+    "oneOf",
+    "not",
+    "$ref",
+];
+
+/// Keywords without effect on validation
+const ANNOTATIONS: &[&str] = &[
+    "$schema",
+    "$id",
+    "$comment",
+    "$defs",
+    "definitions",
+    "title",
+    "description",
+    "default",
+    "examples",
+    "deprecated",
+    "readOnly",
+    "writeOnly",
+];
+
+/// Panic when schema or one of its subschemas uses a keyword validate does not check
+fn check_keywords(schema: &Value) {
+    let object = match schema {
+        Value::Bool(_) => return,
+        Value::Object(object) => object,
+        // e.g. array form "items": [...] of tuple validation
+        _ => panic!(
+            "unsupported JSON Schema {}, schemas must be objects or booleans",
+            schema
+        ),
+    };
+    for (keyword, subschema) in object.iter() {
+        match keyword.as_str() {
+            // boolean form of draft 4
+            "exclusiveMinimum" | "exclusiveMaximum" if !subschema.is_number() => panic!(
+                "unsupported JSON Schema `{}` {}, only numbers are supported",
+                keyword, subschema
+            ),
+            "properties" | "$defs" | "definitions" => subschema
+                .as_object()
+                .into_iter()
+                .flatten()
+                .for_each(|(_, subschema)| check_keywords(subschema)),
+            "allOf" | "anyOf" | "oneOf" => subschema
+                .as_array()
+                .into_iter()
+                .flatten()
+                .for_each(check_keywords),
+            "additionalProperties" | "items" | "not" => check_keywords(subschema),
+            keyword if KEYWORDS.contains(&keyword) || ANNOTATIONS.contains(&keyword) => {}
+            keyword => panic!("unsupported JSON Schema keyword `{}`", keyword),
+        }
+    }
+}
+
+/// Returns true when value conforms to schema
+fn conforms(value: &Value, schema: &Value, root: &Value, pointer: &str) -> bool {
+    let mut errors = vec![];
+    validate(value, schema, root, pointer, &mut errors);
+    errors.is_empty()
+}
+
+/// Append errors of value at pointer against schema, e.g. "/user/id: expected integer"
+///
+/// root is the document of local $ref, e.g. "#/$defs/user"
+fn validate(value: &Value, schema: &Value, root: &Value, pointer: &str, errors: &mut Vec<String>) {
+    if schema == &Value::Bool(false) {
+        errors.push(format!("{}: not allowed", pointer_or_root(pointer)));
+        return;
+    }
+    if let Some(reference) = schema.get("$ref") {
+        let target = reference
+            .as_str()
+            .and_then(|reference| reference.strip_prefix('#'))
+            .and_then(|reference| root.pointer(reference))
+            .unwrap_or_else(|| panic!("unsupported JSON Schema reference {}", reference));
+        validate(value, target, root, pointer, errors);
+    }
+    for subschema in schema["allOf"].as_array().into_iter().flatten() {
+        validate(value, subschema, root, pointer, errors);
+    }
+    let mut error =
+        |message: String| errors.push(format!("{}: {}", pointer_or_root(pointer), message));
+    if let Some(subschemas) = schema["anyOf"].as_array() {
+        if !subschemas
+            .iter()
+            .any(|subschema| conforms(value, subschema, root, pointer))
+        {
+            error("expected to match a schema of anyOf".to_string());
+        }
+    }
+    if let Some(subschemas) = schema["oneOf"].as_array() {
+        let matched = subschemas
+            .iter()
+            .filter(|subschema| conforms(value, subschema, root, pointer))
+            .count();
+        if matched != 1 {
+            error(format!(
+                "expected to match exactly one schema of oneOf, matched {}",
+                matched
+            ));
+        }
+    }
+    if let Some(subschema) = schema.get("not") {
+        if conforms(value, subschema, root, pointer) {
+            error("expected not to match schema of not".to_string());
+        }
+    }
+    let types: Vec<&str> = match &schema["type"] {
+        Value::String(kind) => vec![kind.as_str()],
+        Value::Array(kinds) => kinds.iter().filter_map(Value::as_str).collect(),
+        _ => vec![],
+    };
+    if !types.is_empty() && !types.iter().any(|kind| has_type(value, kind)) {
+        error(format!("expected {}", types.join(" or ")));
+        return;
+    }
+    if let Some(values) = schema["enum"].as_array() {
+        if !values.contains(value) {
+            error(format!("expected one of {}", schema["enum"]));
+        }
+    }
+    if let Some(expected) = schema.get("const") {
+        if expected != value {
+            error(format!("expected {}", expected));
+        }
+    }
+    match value {
+        Value::Object(object) => {
+            check_bounds(
+                object.len(),
+                schema,
+                "minProperties",
+                "maxProperties",
+                "properties",
+                &mut error,
+            );
+            for required in schema["required"].as_array().into_iter().flatten() {
+                if let Some(required) = required.as_str().filter(|key| !object.contains_key(*key)) {
+                    error(format!("missing property `{}`", required));
+                }
+            }
+            for (key, property) in object.iter() {
+                let property_pointer = format!("{}/{}", pointer, key);
+                match schema["properties"].get(key) {
+                    Some(property_schema) => {
+                        validate(property, property_schema, root, &property_pointer, errors)
+                    }
+                    None => match &schema["additionalProperties"] {
+                        Value::Bool(false) => {
+                            errors.push(format!("{}: unexpected property", property_pointer))
+                        }
+                        additional @ Value::Object(_) => {
+                            validate(property, additional, root, &property_pointer, errors)
+                        }
+                        _ => {}
+                    },
+                }
+            }
+        }
+        Value::Array(items) => {
+            check_bounds(
+                items.len(),
+                schema,
+                "minItems",
+                "maxItems",
+                "items",
+                &mut error,
+            );
+            if schema["uniqueItems"] == Value::Bool(true)
+                && items
+                    .iter()
+                    .enumerate()
+                    .any(|(index, item)| items[..index].contains(item))
+            {
+                error("expected unique items".to_string());
+            }
+            for (index, item) in items.iter().enumerate() {
+                validate(
+                    item,
+                    &schema["items"],
+                    root,
+                    &format!("{}/{}", pointer, index),
+                    errors,
+                );
+            }
+        }
+        Value::String(string) => {
+            let len = string.chars().count();
+            check_bounds(
+                len,
+                schema,
+                "minLength",
+                "maxLength",
+                "characters",
+                &mut error,
+            );
+        }
+        Value::Number(number) => {
+            let number = number.as_f64().unwrap_or(f64::NAN);
+            if schema["minimum"]
+                .as_f64()
+                .is_some_and(|minimum| number < minimum)
+            {
+                error(format!("expected at least {}", schema["minimum"]));
+            }
+            if schema["maximum"]
+                .as_f64()
+                .is_some_and(|maximum| number > maximum)
+            {
+                error(format!("expected at most {}", schema["maximum"]));
+            }
+            if schema["exclusiveMinimum"]
+                .as_f64()
+                .is_some_and(|minimum| number <= minimum)
+            {
+                error(format!("expected more than {}", schema["exclusiveMinimum"]));
+            }
+            if schema["exclusiveMaximum"]
+                .as_f64()
+                .is_some_and(|maximum| number >= maximum)
+            {
+                error(format!("expected less than {}", schema["exclusiveMaximum"]));
+            }
+        }
+        _ => {}
+    }
+}
+
+fn check_bounds(
+    len: usize,
+    schema: &Value,
+    min: &str,
+    max: &str,
+    unit: &str,
+    error: &mut impl FnMut(String),
+) {
+    if schema[min].as_u64().is_some_and(|min| (len as u64) < min) {
+        error(format!("expected at least {} {}", schema[min], unit));
+    }
+    if schema[max].as_u64().is_some_and(|max| (len as u64) > max) {
+        error(format!("expected at most {} {}", schema[max], unit));
+    }
+}
+
+fn has_type(value: &Value, kind: &str) -> bool {
+    match kind {
+        "null" => value.is_null(),
+        "boolean" => value.is_boolean(),
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "number" => value.is_number(),
+        "integer" => {
+            value.is_i64()
+                || value.is_u64()
+                || value.as_f64().is_some_and(|number| number.fract() == 0.0)
+        }
+        _ => true,
+    }
+}
+
+fn pointer_or_root(pointer: &str) -> &str {
+    if pointer.is_empty() {
+        "/"
+    } else {
+        pointer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::MultiPartFormDataParser;
+
+    fn schema() -> Value {
+        json!({
+            "type": "object",
+            "required": ["title", "tags"],
+            "additionalProperties": false,
+            "properties": {
+                "title": { "type": "string", "minLength": 1 },
+                "pages": { "type": "integer", "minimum": 1 },
+                "tags": { "type": "array", "maxItems": 2, "items": { "enum": ["a", "b"] } }
+            }
+        })
+    }
+
+    #[test]
+    fn test_should_add_valid_json_part() {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder.with_json_validated(
+            "metadata",
+            &json!({ "title": "sample", "pages": 3, "tags": ["a"] }),
+            &schema(),
+        );
+        let ((_, content_type), body) = multipart_form_data_builder.build();
+
+        let parsed = MultiPartFormDataParser::parse(&content_type, &body).unwrap();
+        parsed
+            .assert_part("metadata")
+            .assert_content_type("application/json")
+            .assert_text(r#"{"pages":3,"tags":["a"],"title":"sample"}"#);
+    }
+
+    #[test]
+    fn test_should_list_schema_errors() {
+        let mut errors = vec![];
+        validate(
+            &json!({ "title": "", "pages": 1.5, "tags": ["a", "c", "b"], "extra": 1 }),
+            &schema(),
+            &schema(),
+            "",
+            &mut errors,
+        );
+
+        assert_eq!(
+            errors,
+            [
+                "/extra: unexpected property",
+                "/pages: expected integer",
+                "/tags: expected at most 2 items",
+                "/tags/1: expected one of [\"a\",\"b\"]",
+                "/title: expected at least 1 characters",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_should_check_combinators_and_references() {
+        let schema = json!({
+            "$defs": { "id": { "type": "integer", "exclusiveMinimum": 0 } },
+            "type": "object",
+            "properties": {
+                "id": { "$ref": "#/$defs/id" },
+                "owner": { "oneOf": [{ "type": "string" }, { "$ref": "#/$defs/id" }] },
+                "tags": { "type": "array", "uniqueItems": true, "items": { "not": { "const": "" } } }
+            },
+            "anyOf": [{ "required": ["id"] }, { "required": ["owner"] }]
+        });
+        let validate = |value: Value| {
+            let mut errors = vec![];
+            validate(&value, &schema, &schema, "", &mut errors);
+            errors
+        };
+
+        assert!(validate(json!({ "id": 1, "owner": "some_name", "tags": ["a"] })).is_empty());
+        assert_eq!(
+            validate(json!({ "owner": 0.5, "tags": ["a", "a", ""] })),
+            [
+                "/owner: expected to match exactly one schema of oneOf, matched 0",
+                "/tags: expected unique items",
+                "/tags/2: expected not to match schema of not",
+            ]
+        );
+        assert_eq!(validate(json!({ "id": 0 })), ["/id: expected more than 0"]);
+        assert_eq!(
+            validate(json!({})),
+            ["/: expected to match a schema of anyOf"]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "unsupported JSON Schema keyword `pattern`")]
+    fn test_should_panic_on_unsupported_keywords() {
+        MultiPartFormDataBuilder::new().with_json_validated(
+            "metadata",
+            &json!({ "title": "sample" }),
+            &json!({ "properties": { "title": { "type": "string", "pattern": "^[a-z]+$" } } }),
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "value of `metadata` does not conform to schema: /: missing property `title`"
+    )]
+    fn test_should_panic_on_invalid_json() {
+        MultiPartFormDataBuilder::new().with_json_validated(
+            "metadata",
+            &json!({ "tags": [] }),
+            &schema(),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "unsupported JSON Schema [{\"type\":\"string\"}]")]
+    fn test_should_panic_on_tuple_items() {
+        MultiPartFormDataBuilder::new().with_json_validated(
+            "metadata",
+            &json!({ "tags": ["a"] }),
+            &json!({ "properties": { "tags": { "items": [{ "type": "string" }] } } }),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "unsupported JSON Schema `exclusiveMinimum` true")]
+    fn test_should_panic_on_boolean_exclusive_bounds() {
+        MultiPartFormDataBuilder::new().with_json_validated(
+            "metadata",
+            &json!({ "size": 0 }),
+            &json!({ "properties": { "size": { "minimum": 0, "exclusiveMinimum": true } } }),
+        );
+    }
+}
@@ -0,0 +1,103 @@
+use std::{
+    any::Any,
+    panic::{self, AssertUnwindSafe},
+    sync::{Mutex, OnceLock},
+};
+
+use bytes::Bytes;
+
+/// Init closure of lazy content
+type Init = Box<dyn FnOnce() -> Bytes + Send>;
+
+/// Content produced on first use, shared by clones of its part
+pub(crate) struct LazyContent {
+    content: OnceLock<Bytes>,
+    init: Mutex<Option<Init>>,
+    failure: Mutex<Option<String>>,
+}
+
+impl LazyContent {
+    pub(crate) fn new(init: impl FnOnce() -> Bytes + Send + 'static) -> LazyContent {
+        LazyContent {
+            content: OnceLock::new(),
+            init: Mutex::new(Some(Box::new(init))),
+            failure: Mutex::new(None),
+        }
+    }
+
+    /// Content, init closure runs on first call
+    ///
+    /// Panics when init panics, later calls panic with the message of that first failure, e.g. of
+    /// a failed download
+    pub(crate) fn get(&self) -> &Bytes {
+        self.content.get_or_init(|| {
+            let init = self.init.lock().unwrap().take();
+            let Some(init) = init else {
+                panic!(
+                    "lazy content failed on first use: {}",
+                    self.failure.lock().unwrap().as_deref().unwrap_or_default()
+                );
+            };
+            match panic::catch_unwind(AssertUnwindSafe(init)) {
+                Ok(content) => content,
+                Err(payload) => {
+                    *self.failure.lock().unwrap() = Some(panic_message(payload.as_ref()));
+                    panic::resume_unwind(payload)
+                }
+            }
+        })
+    }
+
+    /// Content when already produced
+    pub(crate) fn get_if_produced(&self) -> Option<&Bytes> {
+        self.content.get()
+    }
+}
+
+/// Message of a panic payload, from panic! with a literal or format arguments
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    match (
+        payload.downcast_ref::<String>(),
+        payload.downcast_ref::<&str>(),
+    ) {
+        (Some(message), _) => message.clone(),
+        (None, Some(message)) => message.to_string(),
+        (None, None) => "init panicked".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_should_produce_content_once() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counted = calls.clone();
+        let lazy = LazyContent::new(move || {
+            counted.fetch_add(1, Ordering::SeqCst);
+            Bytes::from_static(b"content")
+        });
+
+        assert!(lazy.get_if_produced().is_none());
+        assert_eq!(lazy.get().as_ref(), b"content");
+        assert_eq!(lazy.get().as_ref(), b"content");
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_should_keep_first_failure() {
+        let lazy = LazyContent::new(|| panic!("download of http://localhost/a.png failed: 404"));
+
+        for _ in 0..2 {
+            let payload = panic::catch_unwind(AssertUnwindSafe(|| lazy.get())).unwrap_err();
+            assert!(panic_message(payload.as_ref())
+                .contains("download of http://localhost/a.png failed: 404"));
+        }
+    }
+}
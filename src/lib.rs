@@ -1,6 +1,118 @@
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
 use std::path::Path;
+use std::{
+    borrow::Cow,
+    sync::{Arc, Mutex},
+};
 
-use uuid::Uuid;
+pub use bytes::Bytes;
+
+use cache::FileCache;
+use part::{EncodedBody, PartSource};
+
+mod alternative;
+mod assertions;
+#[cfg(feature = "actix-test")]
+mod body_stream;
+mod boundary;
+mod budget;
+mod byteranges;
+mod cache;
+mod cancel;
+mod chunked;
+mod compliance;
+mod diff;
+mod digest;
+#[cfg(feature = "actix")]
+mod echo;
+mod email;
+mod encoding;
+#[cfg(feature = "actix")]
+mod extract;
+mod fuzz;
+mod golden;
+mod graphql;
+mod http;
+#[cfg(feature = "json-schema")]
+mod json_schema;
+mod lazy;
+mod limits;
+mod malformed;
+mod merge;
+mod message;
+mod mime;
+mod mixed;
+#[cfg(feature = "actix-multipart")]
+mod multipart;
+mod mutate;
+mod nested;
+#[cfg(feature = "openapi")]
+mod openapi;
+#[cfg(feature = "pact")]
+mod pact;
+mod padding;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod parser;
+mod part;
+pub mod presets;
+mod related;
+#[cfg(feature = "remote")]
+mod remote;
+mod rng;
+mod snapshot;
+mod stats;
+mod stream;
+mod tcp;
+#[cfg(feature = "actix-test")]
+mod test_server;
+#[cfg(feature = "tracing")]
+mod trace;
+
+pub use alternative::MultipartAlternativeBuilder;
+#[cfg(feature = "actix-test")]
+pub use body_stream::MultiPartFormDataStream;
+pub use boundary::{validate_boundary, BoundaryError, MAX_BOUNDARY_LEN};
+pub use budget::BuildError;
+pub use byteranges::MultipartByteRangesBuilder;
+pub use cancel::CancellationToken;
+pub use compliance::ComplianceMode;
+pub use diff::{MultipartDiff, PartChange, PartDiff};
+pub use digest::PayloadDigest;
+#[cfg(feature = "actix")]
+pub use echo::{echo_multipart_service, EchoPart, EchoSummary};
+pub use email::EmailMimeBuilder;
+#[cfg(feature = "unicode-normalization")]
+pub use encoding::FileNameNormalization;
+pub use encoding::{
+    BodyEncoding, ControlCharacterPolicy, FieldNameEncoding, SpecialCharacterPolicy,
+};
+#[cfg(feature = "actix")]
+pub use extract::call_multipart;
+pub use fuzz::{FormDescription, PartDescription};
+pub use graphql::GraphqlMultipartBuilder;
+pub use limits::FormLimits;
+pub use malformed::{
+    BoundaryInjection, DelimiterCorruption, InvalidBoundary, MalformedMultipartBuilder,
+    TerminatorCorruption,
+};
+pub use merge::MergePolicy;
+pub use mime::MimePart;
+pub use mixed::MultipartMixedBuilder;
+#[cfg(feature = "openapi")]
+pub use openapi::OpenApiViolation;
+pub use padding::{PaddingStrategy, PADDING_BYTE};
+pub use parser::{MultiPartFormDataParser, ParseError, ParsedMultiPartFormData, ParsedPart};
+pub use part::{Part, DEFAULT_CONTENT_TYPE};
+pub use related::MultipartRelatedBuilder;
+#[cfg(feature = "remote")]
+pub use remote::clear_download_cache;
+pub use snapshot::SNAPSHOT_BOUNDARY;
+pub use stats::{FormStats, PartStats};
+pub use stream::MultiPartFormDataReader;
+pub use tcp::RawTcpSender;
+#[cfg(feature = "actix-test")]
+pub use test_server::{post_multipart, post_multipart_stream};
 
 /// Simple builder for multipart/form-data test
 ///
@@ -42,19 +154,125 @@ use uuid::Uuid;
 /// }
 /// ```
 pub struct MultiPartFormDataBuilder {
-    files: Vec<(String, String, String, Box<dyn AsRef<Path>>)>,
-    texts: Vec<(String, String, String)>,
+    parts: Vec<Part>,
+    body_encoding: BodyEncoding,
+    field_name_encoding: FieldNameEncoding,
+    special_character_policy: SpecialCharacterPolicy,
+    control_character_policy: ControlCharacterPolicy,
+    normalize_newlines: bool,
+    request_headers: Vec<(String, String)>,
+    total_size: Option<(usize, PaddingStrategy)>,
+    max_total_size: Option<usize>,
+    compliance_mode: ComplianceMode,
+    file_cache: Option<FileCache>,
+    boundary: Option<String>,
+    golden: bool,
+    boundary_generator: Option<BoundaryGenerator>,
+    last_boundary: Mutex<Option<String>>,
+    progress: Option<stream::Progress>,
+    cancellation: Option<CancellationToken>,
+}
+
+type BoundaryGenerator = Box<dyn Fn() -> String + Send + Sync>;
+
+/// Built multipart/form-data request
+///
+/// headers are ("Content-Type", "multipart/form-data; boundary=...") and ("Content-Length", "..."),
+/// followed by request headers
+///
+/// body is binary data
+pub struct MultiPartFormData {
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl MultiPartFormData {
+    /// Get header value by name, case insensitive
+    ///
+    /// Returns Some(value) when header is present
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header_name, _)| header_name.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+impl Default for MultiPartFormDataBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl MultiPartFormDataBuilder {
     /// Create new MultiPartFormDataBuilder
     pub fn new() -> MultiPartFormDataBuilder {
         MultiPartFormDataBuilder {
-            files: vec![],
-            texts: vec![],
+            parts: vec![],
+            body_encoding: BodyEncoding::Identity,
+            field_name_encoding: FieldNameEncoding::Raw,
+            special_character_policy: SpecialCharacterPolicy::Quote,
+            control_character_policy: ControlCharacterPolicy::PercentEncode,
+            normalize_newlines: false,
+            request_headers: vec![],
+            total_size: None,
+            max_total_size: None,
+            compliance_mode: ComplianceMode::Lenient,
+            file_cache: None,
+            boundary: None,
+            golden: false,
+            boundary_generator: None,
+            last_boundary: Mutex::new(None),
+            progress: None,
+            cancellation: None,
         }
     }
 
+    /// Add part to multipart/form-data
+    ///
+    /// part is part created with Part::text, Part::file, ...
+    ///
+    /// Returns &mut MultiPartFormDataBuilder
+    pub fn with_part(&mut self, part: Part) -> &mut MultiPartFormDataBuilder {
+        self.parts.push(part);
+        self
+    }
+
+    /// Add the same part many times to multipart/form-data
+    ///
+    /// template is repeated part, its content is shared between repetitions
+    ///
+    /// count is number of repetitions
+    ///
+    /// Returns &mut MultiPartFormDataBuilder
+    pub fn with_repeated_parts(
+        &mut self,
+        template: &Part,
+        count: usize,
+    ) -> &mut MultiPartFormDataBuilder {
+        self.parts
+            .extend(std::iter::repeat_n(template, count).cloned());
+        self
+    }
+
+    /// Add the same text many times to multipart/form-data, e.g. for duplicate field limits
+    ///
+    /// name is form name
+    ///
+    /// value is form value, allocated once and shared between repetitions
+    ///
+    /// count is number of repetitions
+    ///
+    /// Returns &mut MultiPartFormDataBuilder
+    pub fn with_repeated_text(
+        &mut self,
+        name: impl Into<String>,
+        value: impl Into<String>,
+        count: usize,
+    ) -> &mut MultiPartFormDataBuilder {
+        self.with_repeated_parts(&Part::text(name, value), count)
+    }
+
     /// Add text to multipart/form-data
     ///
     /// name is form name
@@ -67,11 +285,54 @@ impl MultiPartFormDataBuilder {
         name: impl Into<String>,
         value: impl Into<String>,
     ) -> &mut MultiPartFormDataBuilder {
-        self.texts
-            .push((name.into(), value.into(), "text/plain".to_string()));
+        self.with_part(Part::text(name, value))
+    }
+
+    /// Shuffle order of added parts, e.g. to assert handlers are order independent
+    ///
+    /// seed makes the order reproducible, same seed and parts give same order
+    ///
+    /// Returns &mut MultiPartFormDataBuilder
+    pub fn shuffle_parts(&mut self, seed: u64) -> &mut MultiPartFormDataBuilder {
+        rng::Rng::new(seed).shuffle(&mut self.parts);
         self
     }
 
+    /// Add text to multipart/form-data only when value is Some
+    ///
+    /// name is form name
+    ///
+    /// value is optional form value
+    ///
+    /// Returns &mut MultiPartFormDataBuilder
+    pub fn with_optional_text(
+        &mut self,
+        name: impl Into<String>,
+        value: Option<impl Into<String>>,
+    ) -> &mut MultiPartFormDataBuilder {
+        match value {
+            Some(value) => self.with_text(name, value),
+            None => self,
+        }
+    }
+
+    /// Add text with the value of an environment variable to multipart/form-data, see
+    /// Part::env_text
+    ///
+    /// name is form name
+    ///
+    /// env_var is environment variable name, read on first build
+    ///
+    /// default is the value when env_var is not set
+    pub fn with_env_text(
+        &mut self,
+        name: impl Into<String>,
+        env_var: impl Into<String>,
+        default: Option<&str>,
+    ) -> &mut MultiPartFormDataBuilder {
+        self.with_part(Part::env_text(name, env_var, default))
+    }
+
     /// Add file to multipart/form-data
     ///
     /// path is file path
@@ -81,6 +342,7 @@ impl MultiPartFormDataBuilder {
     /// content_type is file content type
     ///
     /// file_name is file name
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
     pub fn with_file(
         &mut self,
         path: impl AsRef<Path> + 'static,
@@ -88,17 +350,345 @@ impl MultiPartFormDataBuilder {
         content_type: impl Into<String>,
         file_name: impl Into<String>,
     ) -> &mut MultiPartFormDataBuilder {
-        self.files.push((
-            name.into(),
-            file_name.into(),
-            content_type.into(),
-            Box::new(path),
-        ));
+        self.with_part(Part::file(path, name, content_type, file_name))
+    }
+
+    /// Add file with content type "application/octet-stream" to multipart/form-data
+    ///
+    /// path is file path
+    ///
+    /// name is form name
+    ///
+    /// file_name is file name
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    pub fn with_file_default_type(
+        &mut self,
+        path: impl AsRef<Path>,
+        name: impl Into<String>,
+        file_name: impl Into<String>,
+    ) -> &mut MultiPartFormDataBuilder {
+        self.with_part(Part::file_default_type(path, name, file_name))
+    }
+
+    /// Add file to multipart/form-data only when path exists, e.g. for optional fixtures
+    ///
+    /// path is file path
+    ///
+    /// name is form name
+    ///
+    /// content_type is file content type
+    ///
+    /// file_name is file name
+    ///
+    /// Returns true when file was added
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    pub fn with_file_if_exists(
+        &mut self,
+        path: impl AsRef<Path>,
+        name: impl Into<String>,
+        content_type: impl Into<String>,
+        file_name: impl Into<String>,
+    ) -> bool {
+        let exists = path.as_ref().is_file();
+        if exists {
+            self.with_part(Part::file(path, name, content_type, file_name));
+        }
+        exists
+    }
+
+    /// Add text to multipart/form-data without copying the value
+    ///
+    /// name is form name
+    ///
+    /// value is form value, borrowed static strings are never copied
+    ///
+    /// Returns &mut MultiPartFormDataBuilder
+    pub fn with_static_text(
+        &mut self,
+        name: impl Into<String>,
+        value: impl Into<Cow<'static, str>>,
+    ) -> &mut MultiPartFormDataBuilder {
+        self.with_part(Part::static_text(name, value))
+    }
+
+    /// Add file part sent by browsers for an empty file input to multipart/form-data
+    ///
+    /// name is form name
+    ///
+    /// file name is "", content type is "application/octet-stream" and content is empty
+    ///
+    /// Returns &mut MultiPartFormDataBuilder
+    pub fn with_empty_file(&mut self, name: impl Into<String>) -> &mut MultiPartFormDataBuilder {
+        self.with_part(Part::empty_file(name))
+    }
+
+    /// Add file from in memory content to multipart/form-data, content is shared without copying
+    ///
+    /// name is form name
+    ///
+    /// file_name is file name
+    ///
+    /// content_type is file content type
+    ///
+    /// content is file content, e.g. Bytes, Vec<u8> or &'static [u8]
+    pub fn with_bytes(
+        &mut self,
+        name: impl Into<String>,
+        file_name: impl Into<String>,
+        content_type: impl Into<String>,
+        content: impl Into<Bytes>,
+    ) -> &mut MultiPartFormDataBuilder {
+        self.with_part(Part::bytes(name, file_name, content_type, content))
+    }
+
+    /// Add file from static content to multipart/form-data, e.g. include_bytes!
+    ///
+    /// name is form name
+    ///
+    /// file_name is file name
+    ///
+    /// content_type is file content type
+    ///
+    /// content is file content, never copied until build
+    pub fn with_static_file(
+        &mut self,
+        name: impl Into<String>,
+        file_name: impl Into<String>,
+        content_type: impl Into<String>,
+        content: &'static [u8],
+    ) -> &mut MultiPartFormDataBuilder {
+        self.with_part(Part::static_file(name, file_name, content_type, content))
+    }
+
+    /// Add file with content produced on first build to multipart/form-data, see Part::lazy
+    ///
+    /// name is form name
+    ///
+    /// file_name is file name
+    ///
+    /// content_type is file content type
+    ///
+    /// init produces file content, e.g. Vec<u8> or Bytes, only called when the form is built
+    pub fn with_lazy<C: Into<Bytes>>(
+        &mut self,
+        name: impl Into<String>,
+        file_name: impl Into<String>,
+        content_type: impl Into<String>,
+        init: impl FnOnce() -> C + Send + 'static,
+    ) -> &mut MultiPartFormDataBuilder {
+        self.with_part(Part::lazy(name, file_name, content_type, init))
+    }
+
+    /// Add text with raw bytes to multipart/form-data
+    ///
+    /// name is form name, written as is, may be invalid UTF-8
+    ///
+    /// value is form value, written as is, may be invalid UTF-8
+    ///
+    /// Returns &mut MultiPartFormDataBuilder
+    pub fn with_raw_text(
+        &mut self,
+        name: impl Into<Vec<u8>>,
+        value: impl Into<Vec<u8>>,
+    ) -> &mut MultiPartFormDataBuilder {
+        self.with_part(Part::raw_text(name, value))
+    }
+
+    /// Add file with raw bytes name and file name to multipart/form-data
+    ///
+    /// path is file path
+    ///
+    /// name is form name, written as is, may be invalid UTF-8
+    ///
+    /// content_type is file content type
+    ///
+    /// file_name is file name, written as is, may be invalid UTF-8
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    pub fn with_raw_file(
+        &mut self,
+        path: impl AsRef<Path> + 'static,
+        name: impl Into<Vec<u8>>,
+        content_type: impl Into<String>,
+        file_name: impl Into<Vec<u8>>,
+    ) -> &mut MultiPartFormDataBuilder {
+        self.with_part(Part::raw_file(path, name, content_type, file_name))
+    }
+
+    /// Set encoding applied to the whole body by build_form_data
+    ///
+    /// body_encoding is body encoding, Content-Encoding header is added when not Identity
+    pub fn with_body_encoding(
+        &mut self,
+        body_encoding: BodyEncoding,
+    ) -> &mut MultiPartFormDataBuilder {
+        self.body_encoding = body_encoding;
+        self
+    }
+
+    /// Add request header to build_form_data output, e.g. auth token or idempotency key
+    ///
+    /// Request headers are also sent by to_raw_http_request and post_multipart
+    pub fn with_request_header(
+        &mut self,
+        name: impl Into<String>,
+        value: impl Into<String>,
+    ) -> &mut MultiPartFormDataBuilder {
+        self.request_headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Set encoding of field names, applied to every part on build
+    ///
+    /// field_name_encoding is field name encoding, default is Raw
+    pub fn with_field_name_encoding(
+        &mut self,
+        field_name_encoding: FieldNameEncoding,
+    ) -> &mut MultiPartFormDataBuilder {
+        self.field_name_encoding = field_name_encoding;
+        self
+    }
+
+    /// Set policy for field names with space, ';', '=', '"' or '\\', applied to every part on build
+    ///
+    /// special_character_policy is special character policy, default is Quote
+    pub fn with_special_character_policy(
+        &mut self,
+        special_character_policy: SpecialCharacterPolicy,
+    ) -> &mut MultiPartFormDataBuilder {
+        self.special_character_policy = special_character_policy;
+        self
+    }
+
+    /// Set policy for file names with control characters, applied to every part that is not raw
+    /// on build
+    ///
+    /// control_character_policy is control character policy, default is PercentEncode
+    pub fn with_control_character_policy(
+        &mut self,
+        control_character_policy: ControlCharacterPolicy,
+    ) -> &mut MultiPartFormDataBuilder {
+        self.control_character_policy = control_character_policy;
+        self
+    }
+
+    /// Normalize "\r" and "\n" in text values to "\r\n" on build, like browsers do on submission
+    ///
+    /// Only parts without a file name and with in memory content are normalized
+    pub fn with_normalized_newlines(&mut self) -> &mut MultiPartFormDataBuilder {
+        self.normalize_newlines = true;
+        self
+    }
+
+    /// Call progress with (written, total) bytes while building or reading the body, e.g. for
+    /// long running uploads
+    ///
+    /// File contents are then read in chunks without the file cache, progress is called after
+    /// each chunk
+    pub fn on_progress(
+        &mut self,
+        progress: impl Fn(u64, u64) + Send + Sync + 'static,
+    ) -> &mut MultiPartFormDataBuilder {
+        self.progress = Some(Arc::new(progress));
+        self
+    }
+
+    /// Abort readers of build_reader and build_to_writer once cancellation_token is cancelled
+    ///
+    /// Reads then fail with a ConnectionAborted error, e.g. to abort a streamed upload midway
+    pub fn with_cancellation(
+        &mut self,
+        cancellation_token: CancellationToken,
+    ) -> &mut MultiPartFormDataBuilder {
+        self.cancellation = Some(cancellation_token);
+        self
+    }
+
+    /// Set a fixed boundary used by every build, replaces boundary generator
+    ///
+    /// boundary is checked against RFC 2046 on build, builds panic and try_build returns
+    /// BuildError::InvalidBoundary when it is invalid, see try_with_boundary to check it when set
+    pub fn with_boundary(&mut self, boundary: impl Into<String>) -> &mut MultiPartFormDataBuilder {
+        self.boundary = Some(boundary.into());
+        self
+    }
+
+    /// Set a fixed boundary used by every build after validating it against RFC 2046
+    ///
+    /// boundary is quoted in the Content-Type header when it contains spaces or characters like
+    /// ':' or '='
+    ///
+    /// Returns BoundaryError and keeps the current boundary when boundary is invalid
+    pub fn try_with_boundary(
+        &mut self,
+        boundary: impl Into<String>,
+    ) -> Result<&mut MultiPartFormDataBuilder, BoundaryError> {
+        let boundary = boundary.into();
+        boundary::validate_boundary(&boundary)?;
+        Ok(self.with_boundary(boundary))
+    }
+
+    /// Boundary of builds
+    ///
+    /// Returns fixed boundary when set, otherwise boundary of last build, None before first build
+    ///
+    /// Builds are build, try_build, build_parts, build_reader and their variants, renders like
+    /// Display, stats or to_curl_command do not change it
+    pub fn boundary(&self) -> Option<String> {
+        self.boundary
+            .clone()
+            .or_else(|| self.last_boundary.lock().unwrap().clone())
+    }
+
+    /// Set the function generating a boundary on each build
+    ///
+    /// boundary_generator replaces the default random boundary, e.g. for deterministic tests
+    ///
+    /// Generated boundaries are checked against RFC 2046 like with_boundary
+    pub fn with_boundary_generator(
+        &mut self,
+        boundary_generator: impl Fn() -> String + Send + Sync + 'static,
+    ) -> &mut MultiPartFormDataBuilder {
+        self.boundary = None;
+        self.boundary_generator = Some(Box::new(boundary_generator));
+        self
+    }
+
+    /// Cache file contents across builds, e.g. when the same builder is built in a loop
+    ///
+    /// Cached contents are kept in memory for the builder lifetime, so large files use twice their
+    /// size while building
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    pub fn with_file_cache(&mut self) -> &mut MultiPartFormDataBuilder {
+        self.file_cache.get_or_insert_with(FileCache::default);
+        self
+    }
+
+    /// Disable caching of file contents across builds, files are read on each build, the default
+    ///
+    /// A path used by several parts is still read once per build
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    pub fn without_file_cache(&mut self) -> &mut MultiPartFormDataBuilder {
+        self.file_cache = None;
+        self
+    }
+
+    /// Drop cached file contents, files are read again on next build
+    ///
+    /// Cached contents are also refreshed when file modification time or length changes
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    pub fn invalidate_file_cache(&mut self) -> &mut MultiPartFormDataBuilder {
+        if let Some(file_cache) = &self.file_cache {
+            file_cache.clear();
+        }
         self
     }
 
     /// Build multipart/form-data
     ///
+    /// File contents are read on each build unless with_file_cache is used
+    ///
+    /// Parts are written in the order they were added, up to 0.0.3 files were written before texts
+    ///
     /// Returns ((header_name, header_value), body)
     ///
     /// header_name is "Content-Type"
@@ -107,47 +697,197 @@ impl MultiPartFormDataBuilder {
     ///
     /// body is binary data
     pub fn build(&self) -> ((String, String), Vec<u8>) {
-        let boundary = Uuid::new_v4().to_string();
-
         let mut body = vec![];
+        let header = self.build_into(&mut body);
+        (header, body)
+    }
 
-        for file in self.files.iter() {
-            body.extend(format!("--{}\r\n", boundary).as_bytes());
-            body.extend(
-                format!(
-                    "Content-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\n",
-                    file.0, file.1
-                )
-                .as_bytes(),
-            );
-            body.extend(format!("Content-Type: {}\r\n", file.2).as_bytes());
-            let data = std::fs::read(file.3.as_ref()).unwrap();
-            body.extend(format!("Content-Length: {}\r\n\r\n", data.len()).as_bytes());
-            body.extend(data);
-            body.extend("\r\n".as_bytes());
+    /// Build multipart/form-data into body, reusing its allocation
+    ///
+    /// body is cleared before writing, its capacity grows only when the new body is larger
+    ///
+    /// Returns (header_name, header_value) Content-Type header
+    pub fn build_into(&self, body: &mut Vec<u8>) -> (String, String) {
+        self.try_build_into(body)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub(crate) fn try_build_into(
+        &self,
+        body: &mut Vec<u8>,
+    ) -> Result<(String, String), BuildError> {
+        let boundary = self.try_next_boundary()?;
+        let parts = self.checked_parts(&boundary)?;
+        self.record_boundary(&boundary);
+        match &self.progress {
+            Some(progress) => stream::read_body_into(&boundary, &parts, progress.clone(), body),
+            None => part::write_body_into(&boundary, &parts, self.file_cache.as_ref(), body),
         }
+        Ok(content_type_header(&boundary))
+    }
 
-        for text in self.texts.iter() {
-            body.extend(format!("--{}\r\n", boundary).as_bytes());
-            body.extend(
-                format!("Content-Disposition: form-data; name=\"{}\"\r\n", text.0).as_bytes(),
-            );
-            body.extend(format!("Content-Type: {}\r\n", text.2).as_bytes());
-            let data = text.1.as_bytes();
-            body.extend(format!("Content-Length: {}\r\n\r\n", data.len()).as_bytes());
-            body.extend(data);
-            body.extend("\r\n".as_bytes());
+    /// Build multipart/form-data as separately encoded parts, e.g. to send them as distinct writes
+    ///
+    /// Returns ((header_name, header_value), segments)
+    ///
+    /// segments are one fully encoded part each, with delimiter, headers, content and trailing
+    /// CRLF, last segment is the closing delimiter, concatenated they are the build body
+    pub fn build_parts(&self) -> ((String, String), Vec<Bytes>) {
+        let boundary = self.next_boundary();
+        let mut segments: Vec<Bytes> = self
+            .checked_parts(&boundary)
+            .unwrap_or_else(|error| panic!("{}", error))
+            .iter()
+            .map(|part| {
+                let mut segment = Vec::with_capacity(part.encoded_len(&boundary));
+                part.write(&boundary, &mut segment, self.file_cache.as_ref());
+                segment.into()
+            })
+            .collect();
+        segments.push(format!("--{}--\r\n", boundary).into());
+        self.record_boundary(&boundary);
+        (content_type_header(&boundary), segments)
+    }
+
+    /// Build multipart/form-data with Content-Type and Content-Length headers
+    ///
+    /// Content-Encoding header is added and body is encoded when body encoding is set, request
+    /// headers follow Content-Length
+    ///
+    /// Returns MultiPartFormData
+    pub fn build_form_data(&self) -> MultiPartFormData {
+        let (content_type, body) = self.build();
+        let mut headers = vec![content_type];
+        if let Some(content_encoding) = self.body_encoding.header_value() {
+            headers.push(("Content-Encoding".to_string(), content_encoding.to_string()));
+        }
+        let body = self.body_encoding.encode(body);
+        headers.push(("Content-Length".to_string(), body.len().to_string()));
+        headers.extend(self.request_headers.iter().cloned());
+        MultiPartFormData { headers, body }
+    }
+
+    /// Boundary of the next build, panics when it is not allowed by RFC 2046
+    pub(crate) fn next_boundary(&self) -> String {
+        self.try_next_boundary()
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Boundary of the next build checked against RFC 2046
+    pub(crate) fn try_next_boundary(&self) -> Result<String, BuildError> {
+        let boundary = self.next_boundary_unchecked();
+        match boundary::validate_boundary(&boundary) {
+            Ok(()) => Ok(boundary),
+            Err(error) => Err(BuildError::InvalidBoundary { boundary, error }),
         }
+    }
 
-        body.extend(format!("--{}--\r\n", boundary).as_bytes());
+    /// Boundary of the next build used as is, only for MalformedMultipartBuilder
+    pub(crate) fn next_boundary_unchecked(&self) -> String {
+        match (&self.boundary, &self.boundary_generator) {
+            (Some(boundary), _) => boundary.clone(),
+            (None, _) if self.golden => panic!("golden mode needs a fixed boundary"),
+            (None, Some(boundary_generator)) => boundary_generator(),
+            (None, None) => boundary::random_boundary(),
+        }
+    }
 
-        let header_value = format!("multipart/form-data; boundary={}", boundary);
-        let header = ("Content-Type".to_string(), header_value);
+    /// Keep boundary of a build for boundary, renders like Display and stats do not call it
+    pub(crate) fn record_boundary(&self, boundary: &str) {
+        *self.last_boundary.lock().unwrap() = Some(boundary.to_string());
+    }
 
-        (header, body)
+    pub(crate) fn encoded_body(&self, boundary: &str) -> EncodedBody {
+        let parts = self
+            .checked_parts(boundary)
+            .unwrap_or_else(|error| panic!("{}", error));
+        self.record_boundary(boundary);
+        EncodedBody::new(boundary, parts.iter())
+    }
+
+    /// Parts as written with boundary, see parts, checked against compliance mode, special and
+    /// control character policies and max total size
+    pub(crate) fn checked_parts(&self, boundary: &str) -> Result<Cow<'_, [Part]>, BuildError> {
+        compliance::check_compliance(&self.parts, self.compliance_mode)?;
+        encoding::check_special_characters(
+            &self.parts,
+            self.field_name_encoding,
+            self.special_character_policy,
+        )?;
+        encoding::check_control_characters(&self.parts, self.control_character_policy)?;
+        let parts = self.parts(boundary);
+        if let Some(max_total_size) = self.max_total_size {
+            budget::check_total_size(boundary, &parts, max_total_size)?;
+        }
+        Ok(parts)
+    }
+
+    /// Parts as written with boundary, with field name encoding, special character policy,
+    /// control character policy, newline normalization and padding applied
+    ///
+    /// Parts are not checked, see checked_parts
+    pub(crate) fn parts(&self, boundary: &str) -> Cow<'_, [Part]> {
+        let has_control_characters = |part: &Part| {
+            !part.raw
+                && part
+                    .file_name
+                    .as_deref()
+                    .is_some_and(encoding::has_control_characters)
+        };
+        if self.field_name_encoding == FieldNameEncoding::Raw
+            && self.special_character_policy == SpecialCharacterPolicy::Quote
+            && (self.control_character_policy == ControlCharacterPolicy::UnsafeVerbatim
+                || !self.parts.iter().any(has_control_characters))
+            && !self.normalize_newlines
+            && self.total_size.is_none()
+        {
+            return Cow::Borrowed(&self.parts);
+        }
+        let mut parts: Vec<Part> = self
+            .parts
+            .iter()
+            .map(|part| {
+                let mut part = Part {
+                    name: self
+                        .special_character_policy
+                        .apply(&self.field_name_encoding.encode(&part.name)),
+                    ..part.clone()
+                };
+                if !part.raw {
+                    part.file_name = part
+                        .file_name
+                        .map(|file_name| self.control_character_policy.apply(&file_name));
+                }
+                if let (true, None) = (self.normalize_newlines, &part.file_name) {
+                    let normalized = match &part.source {
+                        PartSource::Bytes(value) => Some(encoding::normalize_newlines(value)),
+                        PartSource::Lazy(lazy) => Some(encoding::normalize_newlines(lazy.get())),
+                        PartSource::File(_) => None,
+                    };
+                    if let Some(normalized) = normalized {
+                        part.source = PartSource::Bytes(normalized.into());
+                    }
+                }
+                part
+            })
+            .collect();
+        if let Some((total_size, strategy)) = &self.total_size {
+            padding::pad(&mut parts, boundary, *total_size, strategy);
+        }
+        Cow::Owned(parts)
     }
 }
 
+pub(crate) fn content_type_header(boundary: &str) -> (String, String) {
+    (
+        "Content-Type".to_string(),
+        format!(
+            "multipart/form-data; boundary={}",
+            boundary::boundary_parameter(boundary)
+        ),
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -166,6 +906,323 @@ mod tests {
 
         assert_eq!(header.0, "Content-Type");
         assert!(header.1.starts_with("multipart/form-data; boundary="));
-        assert!(body.len() > 0);
+        assert!(!body.is_empty());
+    }
+
+    #[test]
+    fn test_should_build_form_data_with_content_length() {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder.with_text("name", "some_name");
+        let form_data = multipart_form_data_builder.build_form_data();
+
+        assert!(form_data
+            .header("content-type")
+            .unwrap()
+            .starts_with("multipart/form-data; boundary="));
+        assert_eq!(
+            form_data.header("Content-Length").unwrap(),
+            form_data.body.len().to_string()
+        );
+    }
+
+    #[test]
+    fn test_should_build_multipart_form_with_raw_bytes() {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder.with_raw_text(b"caf\xe9".to_vec(), b"\xff\xfe".to_vec());
+        multipart_form_data_builder.with_raw_file(
+            "tests/sample.png",
+            b"sample".to_vec(),
+            "image/png",
+            b"\xe9t\xe9.png".to_vec(),
+        );
+        let (_, body) = multipart_form_data_builder.build();
+
+        let contains = |needle: &[u8]| body.windows(needle.len()).any(|window| window == needle);
+        assert!(contains(b"name=\"caf\xe9\"\r\n"));
+        assert!(contains(b"\r\n\r\n\xff\xfe\r\n"));
+        assert!(contains(b"filename=\"\xe9t\xe9.png\""));
+    }
+
+    #[test]
+    fn test_should_build_multipart_form_with_repeated_parts() {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder.with_repeated_parts(&Part::text("tag", "value"), 20_000);
+        let ((_, content_type), body) = multipart_form_data_builder.build();
+
+        let parsed = MultiPartFormDataParser::parse(&content_type, &body).unwrap();
+        assert_eq!(parsed.parts.len(), 20_000);
+        assert!(parsed
+            .parts_named("tag")
+            .all(|part| part.text() == Some("value")));
+    }
+
+    #[test]
+    fn test_should_build_into_reused_buffer() {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder.with_text("name", "some_name");
+        let mut body = Vec::with_capacity(4096);
+        let pointer = body.as_ptr();
+
+        for _ in 0..3 {
+            let (_, content_type) = multipart_form_data_builder.build_into(&mut body);
+            MultiPartFormDataParser::parse(&content_type, &body)
+                .unwrap()
+                .assert_part_count(1)
+                .assert_field("name", "some_name");
+        }
+        assert_eq!(body.as_ptr(), pointer);
+    }
+
+    #[test]
+    fn test_should_build_multipart_form_with_in_memory_sources() {
+        let static_content: &'static [u8] = include_bytes!("../tests/sample.png");
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder
+            .with_static_file("static", "sample.png", "image/png", static_content)
+            .with_bytes(
+                "bytes",
+                "data.bin",
+                "application/octet-stream",
+                vec![1, 2, 3],
+            )
+            .with_static_text("name", "some_name");
+        let ((_, content_type), body) = multipart_form_data_builder.build();
+
+        let parsed = MultiPartFormDataParser::parse(&content_type, &body).unwrap();
+        parsed
+            .assert_field("name", "some_name")
+            .assert_part("static")
+            .assert_file_name("sample.png")
+            .assert_data(static_content);
+        parsed.assert_part("bytes").assert_data([1, 2, 3]);
+    }
+
+    #[test]
+    fn test_should_produce_lazy_content_only_when_built() {
+        let calls = Arc::new(Mutex::new(0));
+        let counted = calls.clone();
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder.with_lazy("report", "report.csv", "text/csv", move || {
+            *counted.lock().unwrap() += 1;
+            b"id,name\n1,some_name\n".to_vec()
+        });
+        assert_eq!(*calls.lock().unwrap(), 0);
+
+        for _ in 0..2 {
+            let ((_, content_type), body) = multipart_form_data_builder.build();
+            let parsed = MultiPartFormDataParser::parse(&content_type, &body).unwrap();
+            parsed
+                .assert_part("report")
+                .assert_file_name("report.csv")
+                .assert_data(b"id,name\n1,some_name\n");
+        }
+        assert_eq!(*calls.lock().unwrap(), 1);
+    }
+
+    #[test]
+    fn test_should_read_env_text_at_build() {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder
+            // cargo sets CARGO_PKG_NAME for test runs, so the environment is not mutated
+            .with_env_text("package", "CARGO_PKG_NAME", None)
+            .with_env_text(
+                "tenant",
+                "ACTIX_MULTIPART_TEST_UNSET",
+                Some("default_tenant"),
+            );
+        let ((_, content_type), body) = multipart_form_data_builder.build();
+
+        MultiPartFormDataParser::parse(&content_type, &body)
+            .unwrap()
+            .assert_field("package", env!("CARGO_PKG_NAME"))
+            .assert_field("tenant", "default_tenant");
+    }
+
+    #[test]
+    #[should_panic(expected = "environment variable `ACTIX_MULTIPART_TEST_MISSING`")]
+    fn test_should_panic_when_env_text_is_not_set() {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder.with_env_text("token", "ACTIX_MULTIPART_TEST_MISSING", None);
+        multipart_form_data_builder.build();
+    }
+
+    #[test]
+    fn test_should_add_file_only_if_exists() {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+
+        assert!(multipart_form_data_builder.with_file_if_exists(
+            "tests/sample.png",
+            "sample",
+            "image/png",
+            "sample.png"
+        ));
+        assert!(!multipart_form_data_builder.with_file_if_exists(
+            "tests/missing.png",
+            "missing",
+            "image/png",
+            "missing.png"
+        ));
+        let ((_, content_type), body) = multipart_form_data_builder.build();
+
+        let parsed = MultiPartFormDataParser::parse(&content_type, &body).unwrap();
+        parsed.assert_part_count(1).assert_no_part("missing");
+    }
+
+    #[test]
+    fn test_should_add_optional_text_only_if_some() {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder
+            .with_optional_text("lang", Some("en"))
+            .with_optional_text("country", None::<String>);
+        let ((_, content_type), body) = multipart_form_data_builder.build();
+
+        let parsed = MultiPartFormDataParser::parse(&content_type, &body).unwrap();
+        parsed
+            .assert_part_count(1)
+            .assert_field("lang", "en")
+            .assert_no_part("country");
+    }
+
+    #[test]
+    fn test_should_build_parts_as_separate_segments() {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder
+            .with_text("name", "some_name")
+            .with_file("tests/sample.png", "sample", "image/png", "sample.png")
+            .with_boundary("boundary");
+        let (_, segments) = multipart_form_data_builder.build_parts();
+        let (_, body) = multipart_form_data_builder.build();
+
+        assert_eq!(segments.len(), 3);
+        assert!(segments[1]
+            .starts_with(b"--boundary\r\nContent-Disposition: form-data; name=\"sample\""));
+        assert_eq!(segments[2], "--boundary--\r\n");
+        assert_eq!(segments.concat(), body);
+    }
+
+    #[test]
+    fn test_should_normalize_newlines_of_text_values() {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder
+            .with_text("comment", "a\nb\rc\r\nd")
+            .with_bytes("doc", "doc.txt", "text/plain", "a\nb")
+            .with_normalized_newlines();
+        let ((_, content_type), body) = multipart_form_data_builder.build();
+
+        let parsed = MultiPartFormDataParser::parse(&content_type, &body).unwrap();
+        parsed.assert_field("comment", "a\r\nb\r\nc\r\nd");
+        parsed.assert_part("doc").assert_text("a\nb");
+    }
+
+    #[test]
+    fn test_should_build_empty_file_input() {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder.with_empty_file("avatar");
+        let ((_, content_type), body) = multipart_form_data_builder.build();
+
+        assert!(String::from_utf8_lossy(&body).contains(
+            "Content-Disposition: form-data; name=\"avatar\"; filename=\"\"\r\nContent-Type: application/octet-stream\r\n"
+        ));
+        let parsed = MultiPartFormDataParser::parse(&content_type, &body).unwrap();
+        parsed
+            .assert_part("avatar")
+            .assert_file_name("")
+            .assert_len(0);
+    }
+
+    #[test]
+    fn test_should_add_file_with_default_type() {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder
+            .with_file_default_type("tests/sample.png", "sample", "sample.png")
+            .with_part(
+                Part::file_default_type("tests/sample.png", "typed", "sample.png")
+                    .with_content_type("image/png"),
+            );
+        let ((_, content_type), body) = multipart_form_data_builder.build();
+
+        let parsed = MultiPartFormDataParser::parse(&content_type, &body).unwrap();
+        parsed
+            .assert_part("sample")
+            .assert_content_type(DEFAULT_CONTENT_TYPE);
+        parsed.assert_part("typed").assert_content_type("image/png");
+    }
+
+    #[test]
+    fn test_should_add_in_memory_parts_with_default_type() {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder
+            .with_part(Part::bytes_default_type("doc", "doc.bin", "content"))
+            .with_part(Part::lazy_default_type("lazy", "lazy.bin", || "lazy"));
+        let ((_, content_type), body) = multipart_form_data_builder.build();
+
+        let parsed = MultiPartFormDataParser::parse(&content_type, &body).unwrap();
+        parsed
+            .assert_part("doc")
+            .assert_content_type(DEFAULT_CONTENT_TYPE)
+            .assert_data("content");
+        parsed
+            .assert_part("lazy")
+            .assert_content_type(DEFAULT_CONTENT_TYPE)
+            .assert_data("lazy");
+    }
+
+    #[test]
+    fn test_should_shuffle_parts_with_seed() {
+        let names = |seed: u64| {
+            let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+            for name in ["a", "b", "c", "d", "e"] {
+                multipart_form_data_builder.with_text(name, "value");
+            }
+            let ((_, content_type), body) = multipart_form_data_builder.shuffle_parts(seed).build();
+            MultiPartFormDataParser::parse(&content_type, &body)
+                .unwrap()
+                .parts
+                .into_iter()
+                .map(|part| part.name.unwrap())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(names(1), names(1));
+        assert_ne!(names(1), ["a", "b", "c", "d", "e"]);
+    }
+
+    #[test]
+    fn test_should_build_repeated_text() {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder.with_repeated_text("tag", "value", 1_000);
+        let ((_, content_type), body) = multipart_form_data_builder.build();
+
+        let parsed = MultiPartFormDataParser::parse(&content_type, &body).unwrap();
+        parsed.assert_part_count(1_000);
+        assert!(parsed
+            .parts_named("tag")
+            .all(|part| part.text() == Some("value")));
+    }
+
+    #[test]
+    fn test_should_add_request_headers_to_form_data() {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder
+            .with_text("name", "some_name")
+            .with_request_header("Authorization", "Bearer token")
+            .with_request_header("Idempotency-Key", "1");
+        let form_data = multipart_form_data_builder.build_form_data();
+
+        let names: Vec<_> = form_data
+            .headers
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "Content-Type",
+                "Content-Length",
+                "Authorization",
+                "Idempotency-Key"
+            ]
+        );
+        assert_eq!(form_data.header("authorization"), Some("Bearer token"));
     }
 }
@@ -0,0 +1,102 @@
+use crate::{MultiPartFormDataBuilder, PaddingStrategy, Part, PADDING_BYTE};
+
+/// Server limits, e.g. of actix-multipart MultipartFormConfig, for boundary value payloads
+///
+/// Limits left None produce no cases
+#[derive(Clone, Debug, Default)]
+pub struct FormLimits {
+    /// maximum number of parts
+    pub max_parts: Option<usize>,
+    /// maximum content size of a field in bytes
+    pub max_field_size: Option<usize>,
+    /// maximum body size in bytes
+    pub max_total_size: Option<usize>,
+}
+
+impl FormLimits {
+    /// Payloads exactly at each limit, expected to be accepted
+    ///
+    /// Returns (description, builder) pairs
+    pub fn at_limit(&self) -> Vec<(String, MultiPartFormDataBuilder)> {
+        self.cases(0, "at")
+    }
+
+    /// Payloads one over each limit, expected to be rejected
+    ///
+    /// Returns (description, builder) pairs
+    pub fn over_limit(&self) -> Vec<(String, MultiPartFormDataBuilder)> {
+        self.cases(1, "one over")
+    }
+
+    fn cases(&self, over: usize, position: &str) -> Vec<(String, MultiPartFormDataBuilder)> {
+        let mut cases = vec![];
+        if let Some(max_parts) = self.max_parts {
+            let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+            for index in 0..max_parts + over {
+                multipart_form_data_builder.with_text(format!("field{}", index), "a");
+            }
+            cases.push((
+                format!("{} max parts {}", position, max_parts),
+                multipart_form_data_builder,
+            ));
+        }
+        if let Some(max_field_size) = self.max_field_size {
+            let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+            multipart_form_data_builder
+                .with_part(Part::text("field", "").padded_to(max_field_size + over, PADDING_BYTE));
+            cases.push((
+                format!("{} max field size {}", position, max_field_size),
+                multipart_form_data_builder,
+            ));
+        }
+        if let Some(max_total_size) = self.max_total_size {
+            let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+            multipart_form_data_builder.pad_to_total_size(
+                max_total_size + over,
+                PaddingStrategy::FillerPart("filler".to_string()),
+            );
+            cases.push((
+                format!("{} max total size {}", position, max_total_size),
+                multipart_form_data_builder,
+            ));
+        }
+        cases
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MultiPartFormDataParser;
+
+    #[test]
+    fn test_should_build_cases_at_and_over_limits() {
+        let form_limits = FormLimits {
+            max_parts: Some(3),
+            max_field_size: Some(1024),
+            max_total_size: Some(4096),
+        };
+
+        for (over, cases) in [(0, form_limits.at_limit()), (1, form_limits.over_limit())] {
+            assert_eq!(cases.len(), 3);
+            let parsed: Vec<_> = cases
+                .iter()
+                .map(|(_, multipart_form_data_builder)| {
+                    let ((_, content_type), body) = multipart_form_data_builder.build();
+                    (
+                        body.len(),
+                        MultiPartFormDataParser::parse(&content_type, &body).unwrap(),
+                    )
+                })
+                .collect();
+            parsed[0].1.assert_part_count(3 + over);
+            assert_eq!(parsed[1].1.parts[0].data.len(), 1024 + over);
+            assert_eq!(parsed[2].0, 4096 + over);
+        }
+    }
+
+    #[test]
+    fn test_should_build_no_cases_without_limits() {
+        assert!(FormLimits::default().at_limit().is_empty());
+    }
+}
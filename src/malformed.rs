@@ -0,0 +1,814 @@
+use crate::{parser::find, part::EncodedBody, MultiPartFormDataBuilder};
+
+/// Builder for malformed multipart/form-data used in negative tests
+///
+/// Wraps a valid MultiPartFormDataBuilder and applies corruptions in the order they were added
+///
+/// # Examples
+///
+/// ```
+/// use actix_multipart_test::{
+///     DelimiterCorruption, MalformedMultipartBuilder, MultiPartFormDataBuilder,
+/// };
+///
+/// let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+/// multipart_form_data_builder.with_text("name", "some_name");
+///
+/// let mut malformed_multipart_builder = MalformedMultipartBuilder::new(multipart_form_data_builder);
+/// malformed_multipart_builder
+///     .with_delimiter_corruption(DelimiterCorruption::BareLineFeed)
+///     .without_header(0, "Content-Disposition");
+/// let (header, body) = malformed_multipart_builder.build();
+/// ```
+pub struct MalformedMultipartBuilder {
+    builder: MultiPartFormDataBuilder,
+    corruptions: Vec<Corruption>,
+    header_boundary: Option<String>,
+    boundary: Option<String>,
+}
+
+/// Corruption of the delimiter written before each part
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DelimiterCorruption {
+    /// "boundary\r\n" instead of "--boundary\r\n"
+    MissingDashes,
+    /// "--boundary\n" instead of "--boundary\r\n"
+    BareLineFeed,
+    /// "--boundaryX\r\n", boundary does not match the Content-Type one
+    Misspelled,
+}
+
+/// Corruption of the closing delimiter written after the last part
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TerminatorCorruption {
+    /// "--boundary\r\n" instead of "--boundary--\r\n"
+    MissingDashes,
+    /// "--boundary-\r\n" instead of "--boundary--\r\n"
+    SingleDash,
+    /// "--boundaryX--\r\n", boundary does not match the Content-Type one
+    Misspelled,
+    /// no closing delimiter, body ends after the last part content
+    Omitted,
+}
+
+/// Boundary occurrence injected inside part content
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BoundaryInjection {
+    /// "boundary"
+    Bare,
+    /// "--boundary"
+    Dashed,
+    /// "\r\n--boundary", a full delimiter
+    Delimiter,
+    /// "\r\n--boundary--", a full closing delimiter
+    Terminator,
+}
+
+/// Boundaries violating RFC 2046
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InvalidBoundary {
+    /// 71 characters, RFC 2046 allows at most 70
+    TooLong,
+    /// ends with a space, RFC 2046 forbids trailing spaces
+    TrailingSpace,
+    /// contains characters outside the RFC 2046 bchars set
+    IllegalCharacters,
+    /// empty boundary
+    Empty,
+}
+
+impl InvalidBoundary {
+    /// Boundary value
+    pub fn boundary(&self) -> String {
+        match self {
+            InvalidBoundary::TooLong => "b".repeat(71),
+            InvalidBoundary::TrailingSpace => "boundary ".to_string(),
+            InvalidBoundary::IllegalCharacters => "bound@ry<>[]".to_string(),
+            InvalidBoundary::Empty => String::new(),
+        }
+    }
+}
+
+enum Corruption {
+    Delimiter(DelimiterCorruption),
+    MissingHeader {
+        part: usize,
+        name: String,
+    },
+    MissingBlankLine {
+        part: usize,
+    },
+    DuplicateContentDisposition {
+        part: usize,
+        value: String,
+    },
+    DuplicateNameParameter {
+        part: usize,
+        name: String,
+    },
+    DispositionParameter {
+        part: usize,
+        parameter: &'static str,
+        value: String,
+    },
+    ExtraHeader {
+        part: usize,
+        name: String,
+        value: String,
+    },
+    FoldedHeaders {
+        part: usize,
+        width: usize,
+    },
+    Preamble(Vec<u8>),
+    BoundaryInContent {
+        part: usize,
+        injection: BoundaryInjection,
+    },
+    ContentLength {
+        part: usize,
+        len: usize,
+    },
+    Terminator(TerminatorCorruption),
+}
+
+impl Corruption {
+    fn apply(&self, body: &mut EncodedBody) {
+        let boundary = body.boundary.clone();
+        match self {
+            Corruption::Delimiter(corruption) => {
+                body.delimiter = match corruption {
+                    DelimiterCorruption::MissingDashes => format!("{}\r\n", boundary),
+                    DelimiterCorruption::BareLineFeed => format!("--{}\n", boundary),
+                    DelimiterCorruption::Misspelled => format!("--{}X\r\n", boundary),
+                }
+                .into_bytes()
+            }
+            Corruption::MissingHeader { part, name } => {
+                if let Some(part) = body.parts.get_mut(*part) {
+                    part.headers.retain(|(header_name, _)| {
+                        !header_name.eq_ignore_ascii_case(name.as_bytes())
+                    });
+                }
+            }
+            Corruption::MissingBlankLine { part } => {
+                if let Some(part) = body.parts.get_mut(*part) {
+                    part.blank_line = false;
+                }
+            }
+            Corruption::DuplicateContentDisposition { part, value } => {
+                if let Some(part) = body.parts.get_mut(*part) {
+                    let position = part
+                        .headers
+                        .iter()
+                        .position(|(name, _)| name.eq_ignore_ascii_case(b"Content-Disposition"))
+                        .map_or(0, |position| position + 1);
+                    part.headers.insert(
+                        position,
+                        (b"Content-Disposition".to_vec(), value.as_bytes().to_vec()),
+                    );
+                }
+            }
+            Corruption::DuplicateNameParameter { part, name } => {
+                if let Some(part) = body.parts.get_mut(*part) {
+                    for (_, value) in part.headers.iter_mut().filter(|(header_name, _)| {
+                        header_name.eq_ignore_ascii_case(b"Content-Disposition")
+                    }) {
+                        value.extend(format!("; name=\"{}\"", name).as_bytes());
+                    }
+                }
+            }
+            Corruption::DispositionParameter {
+                part,
+                parameter,
+                value,
+            } => {
+                if let Some(part) = body.parts.get_mut(*part) {
+                    for (_, disposition) in part
+                        .headers
+                        .iter_mut()
+                        .filter(|(name, _)| name.eq_ignore_ascii_case(b"Content-Disposition"))
+                    {
+                        replace_parameter(disposition, parameter, value.as_bytes());
+                    }
+                }
+            }
+            Corruption::ExtraHeader { part, name, value } => {
+                if let Some(part) = body.parts.get_mut(*part) {
+                    part.headers
+                        .push((name.as_bytes().to_vec(), value.as_bytes().to_vec()));
+                }
+            }
+            Corruption::FoldedHeaders { part, width } => {
+                if let Some(part) = body.parts.get_mut(*part) {
+                    for (name, value) in part.headers.iter_mut() {
+                        *value = fold(value, name.len() + 2, *width);
+                    }
+                }
+            }
+            Corruption::Preamble(preamble) => {
+                body.preamble = preamble.clone();
+                body.preamble.extend(b"\r\n");
+            }
+            Corruption::BoundaryInContent { part, injection } => {
+                if let Some(part) = body.parts.get_mut(*part) {
+                    let injected = match injection {
+                        BoundaryInjection::Bare => boundary,
+                        BoundaryInjection::Dashed => format!("--{}", boundary),
+                        BoundaryInjection::Delimiter => format!("\r\n--{}", boundary),
+                        BoundaryInjection::Terminator => format!("\r\n--{}--", boundary),
+                    };
+                    let middle = part.content.len() / 2;
+                    part.content.splice(middle..middle, injected.bytes());
+                    let len = part.content.len().to_string().into_bytes();
+                    for (_, value) in part
+                        .headers
+                        .iter_mut()
+                        .filter(|(name, _)| name.eq_ignore_ascii_case(b"Content-Length"))
+                    {
+                        *value = len.clone();
+                    }
+                }
+            }
+            Corruption::ContentLength { part, len } => {
+                if let Some(part) = body.parts.get_mut(*part) {
+                    let len = len.to_string().into_bytes();
+                    match part
+                        .headers
+                        .iter_mut()
+                        .find(|(name, _)| name.eq_ignore_ascii_case(b"Content-Length"))
+                    {
+                        Some((_, value)) => *value = len,
+                        None => part.headers.push((b"Content-Length".to_vec(), len)),
+                    }
+                }
+            }
+            Corruption::Terminator(corruption) => {
+                body.terminator = match corruption {
+                    TerminatorCorruption::MissingDashes => format!("--{}\r\n", boundary),
+                    TerminatorCorruption::SingleDash => format!("--{}-\r\n", boundary),
+                    TerminatorCorruption::Misspelled => format!("--{}X--\r\n", boundary),
+                    TerminatorCorruption::Omitted => String::new(),
+                }
+                .into_bytes()
+            }
+        }
+    }
+}
+
+/// Replace quoted parameter value of a header value, e.g. name="..."
+fn replace_parameter(header_value: &mut Vec<u8>, parameter: &str, value: &[u8]) {
+    let prefix = format!("; {}=\"", parameter);
+    let Some(start) = find(header_value, prefix.as_bytes(), 0).map(|start| start + prefix.len())
+    else {
+        return;
+    };
+    let mut end = start;
+    while end < header_value.len() && header_value[end] != b'"' {
+        end += if header_value[end] == b'\\' { 2 } else { 1 };
+    }
+    header_value.splice(start..end.min(header_value.len()), value.iter().copied());
+}
+
+/// Fold header value at spaces so lines do not exceed width, continuation lines start with the space
+fn fold(value: &[u8], offset: usize, width: usize) -> Vec<u8> {
+    let mut folded = Vec::with_capacity(value.len());
+    let mut line_len = offset;
+    for (index, word) in value.split(|byte| *byte == b' ').enumerate() {
+        if index > 0 {
+            if line_len + 1 + word.len() > width {
+                folded.extend(b"\r\n");
+                line_len = 0;
+            }
+            folded.push(b' ');
+            line_len += 1;
+        }
+        folded.extend(word);
+        line_len += word.len();
+    }
+    folded
+}
+
+fn oversized(len: usize, fill: char) -> String {
+    std::iter::repeat_n(fill, len).collect()
+}
+
+impl From<MultiPartFormDataBuilder> for MalformedMultipartBuilder {
+    fn from(builder: MultiPartFormDataBuilder) -> Self {
+        MalformedMultipartBuilder::new(builder)
+    }
+}
+
+impl MalformedMultipartBuilder {
+    /// Create new MalformedMultipartBuilder wrapping a valid builder
+    pub fn new(builder: MultiPartFormDataBuilder) -> MalformedMultipartBuilder {
+        MalformedMultipartBuilder {
+            builder,
+            corruptions: vec![],
+            header_boundary: None,
+            boundary: None,
+        }
+    }
+
+    /// Corrupt the delimiter written before each part
+    pub fn with_delimiter_corruption(
+        &mut self,
+        corruption: DelimiterCorruption,
+    ) -> &mut MalformedMultipartBuilder {
+        self.corruptions.push(Corruption::Delimiter(corruption));
+        self
+    }
+
+    /// Remove all headers with name from a part
+    ///
+    /// part is part index in the order parts were added
+    ///
+    /// name is header name, case insensitive
+    pub fn without_header(
+        &mut self,
+        part: usize,
+        name: impl Into<String>,
+    ) -> &mut MalformedMultipartBuilder {
+        self.corruptions.push(Corruption::MissingHeader {
+            part,
+            name: name.into(),
+        });
+        self
+    }
+
+    /// Remove the empty line separating part headers from part content
+    ///
+    /// part is part index in the order parts were added
+    pub fn without_blank_line(&mut self, part: usize) -> &mut MalformedMultipartBuilder {
+        self.corruptions.push(Corruption::MissingBlankLine { part });
+        self
+    }
+
+    /// Add a second Content-Disposition header to a part, right after the original one
+    ///
+    /// part is part index in the order parts were added
+    ///
+    /// value is second header value, e.g. "form-data; name=\"other\""
+    pub fn with_duplicate_content_disposition(
+        &mut self,
+        part: usize,
+        value: impl Into<String>,
+    ) -> &mut MalformedMultipartBuilder {
+        self.corruptions
+            .push(Corruption::DuplicateContentDisposition {
+                part,
+                value: value.into(),
+            });
+        self
+    }
+
+    /// Append a second name parameter to the Content-Disposition header of a part
+    ///
+    /// part is part index in the order parts were added
+    ///
+    /// name is second name parameter value
+    pub fn with_duplicate_name_parameter(
+        &mut self,
+        part: usize,
+        name: impl Into<String>,
+    ) -> &mut MalformedMultipartBuilder {
+        self.corruptions.push(Corruption::DuplicateNameParameter {
+            part,
+            name: name.into(),
+        });
+        self
+    }
+
+    /// Replace the name parameter of a part with len fill characters
+    ///
+    /// part is part index in the order parts were added
+    pub fn with_oversized_field_name(
+        &mut self,
+        part: usize,
+        len: usize,
+        fill: char,
+    ) -> &mut MalformedMultipartBuilder {
+        self.corruptions.push(Corruption::DispositionParameter {
+            part,
+            parameter: "name",
+            value: oversized(len, fill),
+        });
+        self
+    }
+
+    /// Replace the filename parameter of a part with len fill characters
+    ///
+    /// part is part index in the order parts were added, parts without filename are unchanged
+    pub fn with_oversized_file_name(
+        &mut self,
+        part: usize,
+        len: usize,
+        fill: char,
+    ) -> &mut MalformedMultipartBuilder {
+        self.corruptions.push(Corruption::DispositionParameter {
+            part,
+            parameter: "filename",
+            value: oversized(len, fill),
+        });
+        self
+    }
+
+    /// Add a header with a value of len fill characters to a part
+    ///
+    /// part is part index in the order parts were added
+    ///
+    /// name is header name
+    pub fn with_oversized_header(
+        &mut self,
+        part: usize,
+        name: impl Into<String>,
+        len: usize,
+        fill: char,
+    ) -> &mut MalformedMultipartBuilder {
+        self.corruptions.push(Corruption::ExtraHeader {
+            part,
+            name: name.into(),
+            value: oversized(len, fill),
+        });
+        self
+    }
+
+    /// Fold long headers of a part with obsolete line folding (obs-fold)
+    ///
+    /// part is part index in the order parts were added
+    ///
+    /// width is maximum line length, lines are broken at spaces and continue with a leading space
+    pub fn with_folded_headers(
+        &mut self,
+        part: usize,
+        width: usize,
+    ) -> &mut MalformedMultipartBuilder {
+        self.corruptions
+            .push(Corruption::FoldedHeaders { part, width });
+        self
+    }
+
+    /// Prepend garbage before the first delimiter
+    ///
+    /// len is garbage length in bytes, a CRLF is added after it
+    ///
+    /// pattern is repeated to fill len bytes
+    pub fn with_preamble(&mut self, len: usize, pattern: &[u8]) -> &mut MalformedMultipartBuilder {
+        let preamble = pattern.iter().copied().cycle().take(len).collect();
+        self.corruptions.push(Corruption::Preamble(preamble));
+        self
+    }
+
+    /// Inject the body boundary in the middle of a part content
+    ///
+    /// part is part index in the order parts were added
+    ///
+    /// injection is the boundary form injected, Content-Length is updated to the new size
+    pub fn with_boundary_in_content(
+        &mut self,
+        part: usize,
+        injection: BoundaryInjection,
+    ) -> &mut MalformedMultipartBuilder {
+        self.corruptions
+            .push(Corruption::BoundaryInContent { part, injection });
+        self
+    }
+
+    /// Declare a Content-Length on a part that disagrees with its content size
+    ///
+    /// part is part index in the order parts were added
+    ///
+    /// len is declared length, content is left untouched
+    pub fn with_content_length(
+        &mut self,
+        part: usize,
+        len: usize,
+    ) -> &mut MalformedMultipartBuilder {
+        self.corruptions
+            .push(Corruption::ContentLength { part, len });
+        self
+    }
+
+    /// Corrupt the closing delimiter written after the last part
+    pub fn with_terminator_corruption(
+        &mut self,
+        corruption: TerminatorCorruption,
+    ) -> &mut MalformedMultipartBuilder {
+        self.corruptions.push(Corruption::Terminator(corruption));
+        self
+    }
+
+    /// Advertise a boundary in the Content-Type header different from the one used in body
+    ///
+    /// boundary is Content-Type header boundary
+    pub fn with_header_boundary(
+        &mut self,
+        boundary: impl Into<String>,
+    ) -> &mut MalformedMultipartBuilder {
+        self.header_boundary = Some(boundary.into());
+        self
+    }
+
+    /// Use a boundary violating RFC 2046 in both Content-Type header and body
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use actix_multipart_test::{
+    ///     InvalidBoundary, MalformedMultipartBuilder, MultiPartFormDataBuilder,
+    /// };
+    ///
+    /// let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+    /// multipart_form_data_builder.with_text("name", "some_name");
+    /// let mut malformed_multipart_builder = MalformedMultipartBuilder::new(multipart_form_data_builder);
+    /// malformed_multipart_builder.with_invalid_boundary(InvalidBoundary::TrailingSpace);
+    /// let ((_, content_type), body) = malformed_multipart_builder.build();
+    ///
+    /// assert_eq!(content_type, "multipart/form-data; boundary=boundary ");
+    /// assert!(body.starts_with(b"--boundary \r\n"));
+    /// ```
+    pub fn with_invalid_boundary(
+        &mut self,
+        invalid_boundary: InvalidBoundary,
+    ) -> &mut MalformedMultipartBuilder {
+        self.boundary = Some(invalid_boundary.boundary());
+        self
+    }
+
+    /// Build malformed multipart/form-data
+    ///
+    /// Returns ((header_name, header_value), body) like MultiPartFormDataBuilder::build
+    pub fn build(&self) -> ((String, String), Vec<u8>) {
+        let boundary = self
+            .boundary
+            .clone()
+            .unwrap_or_else(|| self.builder.next_boundary_unchecked());
+        let mut encoded_body = self.builder.encoded_body(&boundary);
+        for corruption in self.corruptions.iter() {
+            corruption.apply(&mut encoded_body);
+        }
+        // boundary is written as is, even when it would need quoting
+        let header = (
+            "Content-Type".to_string(),
+            format!(
+                "multipart/form-data; boundary={}",
+                self.header_boundary
+                    .as_deref()
+                    .unwrap_or(&encoded_body.boundary)
+            ),
+        );
+        (header, encoded_body.write())
+    }
+
+    /// Build malformed multipart/form-data cut at offset, simulating an interrupted upload
+    ///
+    /// offset is body length in bytes, bodies shorter than offset are returned whole
+    ///
+    /// Returns ((header_name, header_value), body) like MultiPartFormDataBuilder::build
+    pub fn build_truncated(&self, offset: usize) -> ((String, String), Vec<u8>) {
+        let (header, mut body) = self.build();
+        body.truncate(offset);
+        (header, body)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MultiPartFormDataParser, ParseError};
+
+    fn malformed_multipart_builder() -> MalformedMultipartBuilder {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder.with_text("name", "some_name");
+        multipart_form_data_builder.into()
+    }
+
+    #[test]
+    fn test_should_build_valid_body_without_corruptions() {
+        let ((_, content_type), body) = malformed_multipart_builder().build();
+        let parsed = MultiPartFormDataParser::parse(&content_type, &body).unwrap();
+        assert_eq!(parsed.text("name"), Some("some_name"));
+    }
+
+    #[test]
+    fn test_should_corrupt_delimiter() {
+        let mut malformed_multipart_builder = malformed_multipart_builder();
+        malformed_multipart_builder.with_delimiter_corruption(DelimiterCorruption::MissingDashes);
+        let ((_, content_type), body) = malformed_multipart_builder.build();
+
+        assert!(!body.starts_with(b"--"));
+        let parsed = MultiPartFormDataParser::parse(&content_type, &body).unwrap();
+        assert!(parsed.parts.is_empty());
+    }
+
+    #[test]
+    fn test_should_remove_header() {
+        let mut malformed_multipart_builder = malformed_multipart_builder();
+        malformed_multipart_builder.without_header(0, "content-disposition");
+        let ((_, content_type), body) = malformed_multipart_builder.build();
+
+        let parsed = MultiPartFormDataParser::parse(&content_type, &body).unwrap();
+        assert_eq!(parsed.parts[0].name, None);
+        assert_eq!(parsed.parts[0].header("Content-Disposition"), None);
+    }
+
+    #[test]
+    fn test_should_corrupt_terminator() {
+        let mut malformed_multipart_builder = malformed_multipart_builder();
+        malformed_multipart_builder.with_terminator_corruption(TerminatorCorruption::SingleDash);
+        let ((_, content_type), body) = malformed_multipart_builder.build();
+
+        assert!(body.ends_with(b"-\r\n"));
+        assert!(!body.ends_with(b"--\r\n"));
+        assert!(MultiPartFormDataParser::parse(&content_type, &body).is_err());
+    }
+
+    #[test]
+    fn test_should_truncate_body_at_offset() {
+        let malformed_multipart_builder = malformed_multipart_builder();
+        let ((_, content_type), truncated) = malformed_multipart_builder.build_truncated(50);
+
+        assert_eq!(truncated.len(), 50);
+        assert!(MultiPartFormDataParser::parse(&content_type, &truncated).is_err());
+    }
+
+    #[test]
+    fn test_should_omit_terminator() {
+        let mut malformed_multipart_builder = malformed_multipart_builder();
+        malformed_multipart_builder.with_terminator_corruption(TerminatorCorruption::Omitted);
+        let ((_, content_type), body) = malformed_multipart_builder.build();
+
+        assert!(body.ends_with(b"some_name\r\n"));
+        assert_eq!(
+            MultiPartFormDataParser::parse(&content_type, &body),
+            Err(ParseError::MissingClosingDelimiter)
+        );
+    }
+
+    #[test]
+    fn test_should_advertise_mismatched_boundary() {
+        let mut malformed_multipart_builder = malformed_multipart_builder();
+        malformed_multipart_builder.with_header_boundary("other");
+        let ((_, content_type), body) = malformed_multipart_builder.build();
+
+        assert_eq!(content_type, "multipart/form-data; boundary=other");
+        assert!(!body.starts_with(b"--other"));
+        assert_eq!(
+            MultiPartFormDataParser::parse(&content_type, &body),
+            Err(ParseError::MissingOpeningDelimiter)
+        );
+    }
+
+    #[test]
+    fn test_should_remove_blank_line() {
+        let mut malformed_multipart_builder = malformed_multipart_builder();
+        malformed_multipart_builder.without_blank_line(0);
+        let ((_, content_type), body) = malformed_multipart_builder.build();
+
+        assert!(String::from_utf8(body.clone())
+            .unwrap()
+            .contains("Content-Length: 9\r\nsome_name"));
+        assert_eq!(
+            MultiPartFormDataParser::parse(&content_type, &body),
+            Err(ParseError::UnterminatedHeaders)
+        );
+    }
+
+    #[test]
+    fn test_should_duplicate_content_disposition() {
+        let mut malformed_multipart_builder = malformed_multipart_builder();
+        malformed_multipart_builder
+            .with_duplicate_content_disposition(0, "form-data; name=\"other\"")
+            .with_duplicate_name_parameter(0, "smuggled");
+        let ((_, content_type), body) = malformed_multipart_builder.build();
+
+        let parsed = MultiPartFormDataParser::parse(&content_type, &body).unwrap();
+        let dispositions: Vec<&str> = parsed.parts[0]
+            .headers
+            .iter()
+            .filter(|(name, _)| name == "Content-Disposition")
+            .map(|(_, value)| value.as_str())
+            .collect();
+        assert_eq!(
+            dispositions,
+            vec![
+                "form-data; name=\"name\"; name=\"smuggled\"",
+                "form-data; name=\"other\"; name=\"smuggled\""
+            ]
+        );
+    }
+
+    #[test]
+    fn test_should_generate_oversized_headers() {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder.with_text("name", "some_name");
+        multipart_form_data_builder.with_file(
+            "tests/sample.png",
+            "sample",
+            "image/png",
+            "sample.png",
+        );
+        let mut malformed_multipart_builder =
+            MalformedMultipartBuilder::new(multipart_form_data_builder);
+        malformed_multipart_builder
+            .with_oversized_field_name(1, 4096, 'n')
+            .with_oversized_file_name(1, 8192, 'f')
+            .with_oversized_header(0, "X-Large", 16384, 'x');
+        let ((_, content_type), body) = malformed_multipart_builder.build();
+
+        let parsed = MultiPartFormDataParser::parse(&content_type, &body).unwrap();
+        assert_eq!(parsed.parts[0].header("X-Large").unwrap().len(), 16384);
+        assert_eq!(parsed.parts[1].name, Some("n".repeat(4096)));
+        assert_eq!(parsed.parts[1].file_name, Some("f".repeat(8192)));
+    }
+
+    #[test]
+    fn test_should_fold_headers() {
+        let mut malformed_multipart_builder = malformed_multipart_builder();
+        malformed_multipart_builder.with_folded_headers(0, 30);
+        let ((_, content_type), body) = malformed_multipart_builder.build();
+
+        assert!(String::from_utf8(body.clone())
+            .unwrap()
+            .contains("Content-Disposition: form-data;\r\n name=\"name\"\r\n"));
+        assert_eq!(
+            MultiPartFormDataParser::parse(&content_type, &body),
+            Err(ParseError::InvalidHeader(" name=\"name\"".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_should_prepend_preamble() {
+        let mut malformed_multipart_builder = malformed_multipart_builder();
+        malformed_multipart_builder.with_preamble(1024 * 1024, b"garbage ");
+        let ((_, content_type), body) = malformed_multipart_builder.build();
+
+        assert!(body.starts_with(b"garbage garbage "));
+        assert_eq!(&body[1024 * 1024..1024 * 1024 + 4], b"\r\n--");
+        let parsed = MultiPartFormDataParser::parse(&content_type, &body).unwrap();
+        assert_eq!(parsed.text("name"), Some("some_name"));
+    }
+
+    #[test]
+    fn test_should_declare_lying_content_length() {
+        let mut malformed_multipart_builder = malformed_multipart_builder();
+        malformed_multipart_builder.with_content_length(0, 1);
+        let ((_, content_type), body) = malformed_multipart_builder.build();
+
+        let parsed = MultiPartFormDataParser::parse(&content_type, &body).unwrap();
+        parsed
+            .assert_part("name")
+            .assert_header("Content-Length", "1")
+            .assert_text("some_name");
+    }
+
+    #[test]
+    fn test_should_inject_boundary_in_content() {
+        let mut malformed_multipart_builder = malformed_multipart_builder();
+        malformed_multipart_builder.with_boundary_in_content(0, BoundaryInjection::Dashed);
+        let ((_, content_type), body) = malformed_multipart_builder.build();
+
+        let parsed = MultiPartFormDataParser::parse(&content_type, &body).unwrap();
+        let expected = format!("some--{}_name", parsed.boundary);
+        parsed
+            .assert_part("name")
+            .assert_text(&expected)
+            .assert_header("Content-Length", &expected.len().to_string());
+    }
+
+    #[test]
+    fn test_should_split_part_on_injected_delimiter() {
+        let mut malformed_multipart_builder = malformed_multipart_builder();
+        malformed_multipart_builder.with_boundary_in_content(0, BoundaryInjection::Delimiter);
+        let ((_, content_type), body) = malformed_multipart_builder.build();
+
+        assert!(MultiPartFormDataParser::parse(&content_type, &body).is_err());
+    }
+
+    #[test]
+    fn test_should_use_invalid_boundary() {
+        let mut malformed_multipart_builder = malformed_multipart_builder();
+        malformed_multipart_builder.with_invalid_boundary(InvalidBoundary::TooLong);
+        let ((_, content_type), body) = malformed_multipart_builder.build();
+
+        let boundary = "b".repeat(71);
+        assert_eq!(
+            content_type,
+            format!("multipart/form-data; boundary={}", boundary)
+        );
+        assert!(body.starts_with(format!("--{}\r\n", boundary).as_bytes()));
+    }
+
+    #[test]
+    fn test_should_use_empty_boundary() {
+        let mut malformed_multipart_builder = malformed_multipart_builder();
+        malformed_multipart_builder.with_invalid_boundary(InvalidBoundary::Empty);
+        let ((_, content_type), body) = malformed_multipart_builder.build();
+
+        assert_eq!(content_type, "multipart/form-data; boundary=");
+        assert_eq!(
+            MultiPartFormDataParser::parse(&content_type, &body),
+            Err(ParseError::MissingBoundary)
+        );
+    }
+}
@@ -0,0 +1,98 @@
+use crate::MultiPartFormDataBuilder;
+
+/// Policy for parts of a merged builder whose name already exists
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MergePolicy {
+    /// Append all parts, duplicate names are sent as repeated fields
+    #[default]
+    KeepBoth,
+    /// Skip merged parts whose name already exists
+    KeepExisting,
+    /// Remove existing parts whose name is merged, merged parts are appended
+    Replace,
+}
+
+impl MultiPartFormDataBuilder {
+    /// Append parts of other builder, duplicate names are kept
+    ///
+    /// Only parts are merged, settings of other builder are ignored
+    ///
+    /// Returns &mut MultiPartFormDataBuilder
+    pub fn merge(&mut self, other: MultiPartFormDataBuilder) -> &mut MultiPartFormDataBuilder {
+        self.merge_with_policy(other, MergePolicy::KeepBoth)
+    }
+
+    /// Append parts of other builder
+    ///
+    /// merge_policy decides how parts with an existing name are merged
+    ///
+    /// Returns &mut MultiPartFormDataBuilder
+    pub fn merge_with_policy(
+        &mut self,
+        other: MultiPartFormDataBuilder,
+        merge_policy: MergePolicy,
+    ) -> &mut MultiPartFormDataBuilder {
+        match merge_policy {
+            MergePolicy::KeepBoth => self.parts.extend(other.parts),
+            MergePolicy::KeepExisting => {
+                let parts: Vec<_> = other
+                    .parts
+                    .into_iter()
+                    .filter(|part| !self.parts.iter().any(|existing| existing.name == part.name))
+                    .collect();
+                self.parts.extend(parts);
+            }
+            MergePolicy::Replace => {
+                self.parts
+                    .retain(|existing| !other.parts.iter().any(|part| part.name == existing.name));
+                self.parts.extend(other.parts);
+            }
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MultiPartFormDataParser, ParsedMultiPartFormData};
+
+    fn merged(merge_policy: MergePolicy) -> ParsedMultiPartFormData {
+        let mut base = MultiPartFormDataBuilder::new();
+        base.with_text("lang", "en").with_text("name", "base");
+        let mut extras = MultiPartFormDataBuilder::new();
+        extras.with_text("name", "extra").with_text("tag", "new");
+        base.merge_with_policy(extras, merge_policy);
+        let ((_, content_type), body) = base.build();
+        MultiPartFormDataParser::parse(&content_type, &body).unwrap()
+    }
+
+    fn names(parsed: &ParsedMultiPartFormData) -> Vec<(&str, &str)> {
+        parsed
+            .parts
+            .iter()
+            .map(|part| (part.name.as_deref().unwrap(), part.text().unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn test_should_merge_with_policy() {
+        assert_eq!(
+            names(&merged(MergePolicy::KeepBoth)),
+            [
+                ("lang", "en"),
+                ("name", "base"),
+                ("name", "extra"),
+                ("tag", "new")
+            ]
+        );
+        assert_eq!(
+            names(&merged(MergePolicy::KeepExisting)),
+            [("lang", "en"), ("name", "base"), ("tag", "new")]
+        );
+        assert_eq!(
+            names(&merged(MergePolicy::Replace)),
+            [("lang", "en"), ("name", "extra"), ("tag", "new")]
+        );
+    }
+}
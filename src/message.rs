@@ -0,0 +1,70 @@
+use bytes::Bytes;
+
+use crate::{MimePart, Part};
+
+impl Part {
+    /// Create part with an embedded email message, Content-Type is message/rfc822
+    ///
+    /// name is form name
+    ///
+    /// file_name is file name, e.g. "forwarded.eml"
+    ///
+    /// message is complete message with headers, e.g. from MimePart::to_message
+    pub fn message(
+        name: impl Into<String>,
+        file_name: impl Into<String>,
+        message: impl Into<Bytes>,
+    ) -> Part {
+        Part::bytes(name, file_name, "message/rfc822", message)
+    }
+}
+
+impl MimePart {
+    /// Create part with an embedded email message, Content-Type is message/rfc822
+    pub fn message(message: impl Into<Bytes>) -> MimePart {
+        MimePart::new("message/rfc822", message)
+    }
+
+    /// Render part as email message
+    ///
+    /// headers are message headers, e.g. From, To and Subject, written before MIME-Version and
+    /// part headers
+    ///
+    /// Returns message bytes
+    pub fn to_message<'a>(&self, headers: impl IntoIterator<Item = (&'a str, &'a str)>) -> Vec<u8> {
+        let mut message = vec![];
+        for (name, value) in headers {
+            message.extend(format!("{}: {}\r\n", name, value).as_bytes());
+        }
+        message.extend(b"MIME-Version: 1.0\r\n");
+        for (name, value) in self.headers.iter() {
+            message.extend(format!("{}: {}\r\n", name, value).as_bytes());
+        }
+        message.extend(b"\r\n");
+        message.extend(self.content());
+        message
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{MimePart, MultiPartFormDataBuilder, MultiPartFormDataParser, Part};
+
+    #[test]
+    fn test_should_embed_message() {
+        let message = MimePart::new("text/plain; charset=utf-8", "Hello")
+            .to_message([("From", "a@example.com"), ("Subject", "Hi")]);
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder.with_part(Part::message("mail", "forwarded.eml", message));
+        let ((_, content_type), body) = multipart_form_data_builder.build();
+
+        let parsed = MultiPartFormDataParser::parse(&content_type, &body).unwrap();
+        parsed
+            .assert_part("mail")
+            .assert_content_type("message/rfc822")
+            .assert_file_name("forwarded.eml")
+            .assert_text(
+                "From: a@example.com\r\nSubject: Hi\r\nMIME-Version: 1.0\r\nContent-Type: text/plain; charset=utf-8\r\n\r\nHello",
+            );
+    }
+}
@@ -0,0 +1,134 @@
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+use std::path::Path;
+
+use bytes::Bytes;
+
+use crate::part::PartSource;
+
+/// Part of a non form-data multipart body, e.g. multipart/mixed
+///
+/// Headers are written in the order they were added, after Content-Type
+#[derive(Clone)]
+pub struct MimePart {
+    pub(crate) headers: Vec<(String, String)>,
+    pub(crate) source: PartSource,
+}
+
+impl MimePart {
+    /// Create part from in memory content
+    ///
+    /// content_type is Content-Type header value
+    ///
+    /// content is part content, e.g. Bytes, Vec<u8>, String or &'static [u8]
+    pub fn new(content_type: impl Into<String>, content: impl Into<Bytes>) -> MimePart {
+        MimePart {
+            headers: vec![("Content-Type".to_string(), content_type.into())],
+            source: PartSource::Bytes(content.into()),
+        }
+    }
+
+    /// Create part from file, file is read on build
+    ///
+    /// path is file path
+    ///
+    /// content_type is Content-Type header value
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    pub fn file(path: impl AsRef<Path>, content_type: impl Into<String>) -> MimePart {
+        MimePart {
+            headers: vec![("Content-Type".to_string(), content_type.into())],
+            source: PartSource::File(path.as_ref().to_path_buf()),
+        }
+    }
+
+    /// Add header to part
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> MimePart {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Encode content as base64 in lines of 76 characters, adds Content-Transfer-Encoding header
+    pub fn base64(mut self) -> MimePart {
+        let content = base64_lines(&self.content());
+        self.source = PartSource::Bytes(content.into());
+        self.with_header("Content-Transfer-Encoding", "base64")
+    }
+
+    pub(crate) fn content_type(&self) -> &str {
+        &self.headers[0].1
+    }
+
+    pub(crate) fn content(&self) -> Vec<u8> {
+        match &self.source {
+            PartSource::File(path) => std::fs::read(path).unwrap(),
+            PartSource::Bytes(bytes) => bytes.to_vec(),
+            PartSource::Lazy(lazy) => lazy.get().to_vec(),
+        }
+    }
+}
+
+/// Standard base64 with padding, lines of 76 characters separated by CRLF
+pub(crate) fn base64_lines(data: &[u8]) -> Vec<u8> {
+    base64(data)
+        .as_bytes()
+        .chunks(76)
+        .collect::<Vec<_>>()
+        .join(&b"\r\n"[..])
+}
+
+/// Standard base64 with padding
+pub(crate) fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let bytes = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let triple = u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]);
+        for position in 0..4 {
+            if position <= chunk.len() {
+                encoded.push(ALPHABET[(triple >> (18 - 6 * position) & 0x3f) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+/// Write multipart body of mime parts
+pub(crate) fn write_multipart(boundary: &str, parts: &[MimePart]) -> Vec<u8> {
+    let mut body = vec![];
+    for part in parts.iter() {
+        body.extend(format!("--{}\r\n", boundary).as_bytes());
+        for (name, value) in part.headers.iter() {
+            body.extend(format!("{}: {}\r\n", name, value).as_bytes());
+        }
+        body.extend(b"\r\n");
+        body.extend(part.content());
+        body.extend(b"\r\n");
+    }
+    body.extend(format!("--{}--\r\n", boundary).as_bytes());
+    body
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_should_encode_base64_lines() {
+        assert_eq!(base64_lines(b"Man"), b"TWFu");
+        assert_eq!(base64_lines(b"Ma"), b"TWE=");
+        assert_eq!(base64_lines(b"M"), b"TQ==");
+        let lines = base64_lines(&[0; 120]);
+        assert_eq!(
+            lines
+                .split(|byte| *byte == b'\n')
+                .map(<[u8]>::len)
+                .collect::<Vec<_>>(),
+            [77, 77, 8]
+        );
+    }
+}
@@ -0,0 +1,125 @@
+use crate::{
+    boundary::{boundary_parameter, checked_or_random_boundary},
+    mime::write_multipart,
+    MimePart,
+};
+
+/// Simple builder for multipart/mixed test
+///
+/// Parts have a Content-Type but no form-data Content-Disposition
+///
+/// # Examples
+///
+/// ```
+/// use actix_multipart_test::{MimePart, MultipartMixedBuilder};
+///
+/// let mut multipart_mixed_builder = MultipartMixedBuilder::new();
+/// multipart_mixed_builder
+///     .with_part(MimePart::new("application/json", r#"{"event":"created"}"#))
+///     .with_part(MimePart::file("tests/sample.png", "image/png"));
+/// let (header, body) = multipart_mixed_builder.build();
+/// ```
+#[derive(Default)]
+pub struct MultipartMixedBuilder {
+    parts: Vec<MimePart>,
+    boundary: Option<String>,
+}
+
+impl MultipartMixedBuilder {
+    /// Create new MultipartMixedBuilder
+    pub fn new() -> MultipartMixedBuilder {
+        MultipartMixedBuilder {
+            parts: vec![],
+            boundary: None,
+        }
+    }
+
+    /// Set a fixed boundary used by every build, e.g. for snapshot or golden tests
+    ///
+    /// boundary is checked against RFC 2046 on build, builds panic when it is invalid
+    ///
+    /// Returns &mut MultipartMixedBuilder
+    pub fn with_boundary(&mut self, boundary: impl Into<String>) -> &mut MultipartMixedBuilder {
+        self.boundary = Some(boundary.into());
+        self
+    }
+
+    /// Add part to multipart/mixed
+    ///
+    /// Returns &mut MultipartMixedBuilder
+    pub fn with_part(&mut self, part: MimePart) -> &mut MultipartMixedBuilder {
+        self.parts.push(part);
+        self
+    }
+
+    /// Build multipart/mixed
+    ///
+    /// Returns ((header_name, header_value), body)
+    ///
+    /// header_name is "Content-Type"
+    ///
+    /// header_value is "multipart/mixed; boundary=..."
+    ///
+    /// body is binary data
+    pub fn build(&self) -> ((String, String), Vec<u8>) {
+        let boundary = checked_or_random_boundary(self.boundary.as_deref());
+        let header = (
+            "Content-Type".to_string(),
+            format!(
+                "multipart/mixed; boundary={}",
+                boundary_parameter(&boundary)
+            ),
+        );
+        (header, write_multipart(&boundary, &self.parts))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MultiPartFormDataParser;
+
+    #[test]
+    fn test_should_build_multipart_mixed() {
+        let mut multipart_mixed_builder = MultipartMixedBuilder::new();
+        multipart_mixed_builder
+            .with_part(MimePart::new("application/json", r#"{"event":"created"}"#))
+            .with_part(MimePart::file("tests/sample.png", "image/png").with_header("X-Id", "1"));
+        let ((_, content_type), body) = multipart_mixed_builder.build();
+
+        assert!(content_type.starts_with("multipart/mixed; boundary="));
+        let parsed = MultiPartFormDataParser::parse(&content_type, &body).unwrap();
+        assert_eq!(parsed.parts.len(), 2);
+        assert!(parsed
+            .parts
+            .iter()
+            .all(|part| part.header("Content-Disposition").is_none()));
+        assert_eq!(parsed.parts[0].text(), Some(r#"{"event":"created"}"#));
+        parsed.parts[1]
+            .assert_content_type("image/png")
+            .assert_header("X-Id", "1")
+            .assert_data(std::fs::read("tests/sample.png").unwrap());
+    }
+
+    #[test]
+    fn test_should_build_multipart_mixed_with_fixed_boundary() {
+        let mut multipart_mixed_builder = MultipartMixedBuilder::new();
+        multipart_mixed_builder
+            .with_part(MimePart::new("text/plain", "event"))
+            .with_boundary("mixed boundary");
+        let ((_, content_type), body) = multipart_mixed_builder.build();
+
+        assert_eq!(content_type, "multipart/mixed; boundary=\"mixed boundary\"");
+        assert_eq!(body, multipart_mixed_builder.build().1);
+        assert!(body.starts_with(b"--mixed boundary\r\n"));
+        assert!(body.ends_with(b"\r\n--mixed boundary--\r\n"));
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid boundary \"bound@ry\"")]
+    fn test_should_panic_on_invalid_mixed_boundary() {
+        MultipartMixedBuilder::new()
+            .with_boundary("bound@ry")
+            .build();
+    }
+}
@@ -0,0 +1,99 @@
+use actix_multipart::Multipart;
+use actix_web::{
+    error::PayloadError,
+    http::header::{HeaderMap, HeaderValue, CONTENT_TYPE},
+    web::Bytes,
+};
+use futures_util::{stream, StreamExt};
+
+use crate::MultiPartFormDataBuilder;
+
+impl MultiPartFormDataBuilder {
+    /// Build multipart/form-data into an actix_multipart::Multipart fed by the body, e.g. for unit
+    /// tests of functions accepting Multipart
+    ///
+    /// Requires "actix-multipart" feature
+    pub fn into_multipart(self) -> Multipart {
+        let ((_, content_type), body) = self.build();
+        multipart(&content_type, body)
+    }
+
+    /// Assert built body is parsable by actix_multipart::Multipart
+    ///
+    /// Panics with the actix-multipart error when a field or chunk fails to parse
+    pub async fn assert_parsable(&self) {
+        let ((_, content_type), body) = self.build();
+        let mut multipart = multipart(&content_type, body);
+        while let Some(field) = multipart.next().await {
+            let mut field = match field {
+                Ok(field) => field,
+                Err(error) => panic!("multipart body is not parsable: {}", error),
+            };
+            while let Some(chunk) = field.next().await {
+                if let Err(error) = chunk {
+                    panic!(
+                        "multipart field {:?} is not parsable: {}",
+                        field.name(),
+                        error
+                    );
+                }
+            }
+        }
+    }
+}
+
+fn multipart(content_type: &str, body: Vec<u8>) -> Multipart {
+    let mut headers = HeaderMap::new();
+    headers.insert(CONTENT_TYPE, HeaderValue::from_str(content_type).unwrap());
+    let payload = stream::once(async move { Ok::<_, PayloadError>(Bytes::from(body)) });
+    Multipart::new(&headers, payload)
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_util::StreamExt;
+
+    use crate::{MultiPartFormDataBuilder, Part};
+
+    #[actix_web::test]
+    async fn test_should_be_parsable() {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder.with_file(
+            "tests/sample.png",
+            "sample",
+            "image/png",
+            "sample.png",
+        );
+        multipart_form_data_builder.with_text("name", "some_name");
+
+        multipart_form_data_builder.assert_parsable().await;
+    }
+
+    #[actix_web::test]
+    async fn test_should_build_multipart() {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder.with_text("name", "some_name");
+
+        let mut multipart = multipart_form_data_builder.into_multipart();
+        let mut field = multipart.next().await.unwrap().unwrap();
+        assert_eq!(field.name(), "name");
+        let mut content = vec![];
+        while let Some(chunk) = field.next().await {
+            content.extend_from_slice(&chunk.unwrap());
+        }
+        drop(field);
+        assert_eq!(content, b"some_name");
+        assert!(multipart.next().await.is_none());
+    }
+
+    #[actix_web::test]
+    #[should_panic(expected = "multipart body is not parsable")]
+    async fn test_should_panic_when_not_parsable() {
+        // part Content-Length keeps boundary collisions parsable, nested multipart is rejected
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder
+            .with_part(Part::text("name", "some_name").with_content_type("multipart/mixed"));
+
+        multipart_form_data_builder.assert_parsable().await;
+    }
+}
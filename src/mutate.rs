@@ -0,0 +1,68 @@
+use std::collections::BTreeSet;
+
+use crate::{rng::Rng, MultiPartFormDataBuilder};
+
+impl MultiPartFormDataBuilder {
+    /// Build multipart/form-data and flip n_flips bytes of the body, e.g. to test parser error
+    /// handling
+    ///
+    /// seed selects flipped positions and values, the same seed, parts and boundary give the same
+    /// body, so set with_boundary for reproducible mutations
+    ///
+    /// Positions are distinct, every flipped byte differs from the original, n_flips is capped at
+    /// the body length
+    ///
+    /// Returns ((header_name, header_value), body), Content-Type header is never mutated
+    pub fn mutate(&self, seed: u64, n_flips: usize) -> ((String, String), Vec<u8>) {
+        let (header, mut body) = self.build();
+        let mut rng = Rng::new(seed);
+        let mut positions = BTreeSet::new();
+        while positions.len() < n_flips.min(body.len()) {
+            let position = rng.below(body.len());
+            if positions.insert(position) {
+                body[position] ^= 1 + rng.below(255) as u8;
+            }
+        }
+        (header, body)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn multipart_form_data_builder() -> MultiPartFormDataBuilder {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder
+            .with_text("name", "some_name")
+            .with_boundary("boundary");
+        multipart_form_data_builder
+    }
+
+    #[test]
+    fn test_should_flip_bytes_reproducibly() {
+        let multipart_form_data_builder = multipart_form_data_builder();
+        let (header, body) = multipart_form_data_builder.build();
+        let (mutated_header, mutated) = multipart_form_data_builder.mutate(42, 5);
+
+        assert_eq!(mutated_header, header);
+        assert_eq!(mutated.len(), body.len());
+        let flips = body
+            .iter()
+            .zip(mutated.iter())
+            .filter(|(a, b)| a != b)
+            .count();
+        assert_eq!(flips, 5);
+        assert_eq!(multipart_form_data_builder.mutate(42, 5).1, mutated);
+        assert_ne!(multipart_form_data_builder.mutate(43, 5).1, mutated);
+    }
+
+    #[test]
+    fn test_should_cap_flips_at_body_length() {
+        let multipart_form_data_builder = multipart_form_data_builder();
+        let (_, body) = multipart_form_data_builder.build();
+        let (_, mutated) = multipart_form_data_builder.mutate(1, usize::MAX);
+
+        assert!(body.iter().zip(mutated.iter()).all(|(a, b)| a != b));
+    }
+}
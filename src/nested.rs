@@ -0,0 +1,77 @@
+use crate::{
+    boundary::random_boundary,
+    part::{Part, PartSource},
+};
+
+impl Part {
+    /// Create part with deeply nested multipart/mixed content for resource limit tests
+    ///
+    /// name is form name
+    ///
+    /// depth is number of nested multipart levels, 0 is a plain text part
+    ///
+    /// fan_out is number of children of each multipart level, leaves are fan_out ^ depth
+    pub fn nested_multipart(name: impl Into<String>, depth: usize, fan_out: usize) -> Part {
+        if depth == 0 {
+            return Part::text(name, "leaf");
+        }
+        let (content_type, content) = nested(depth, fan_out);
+        Part {
+            name: name.into().into_bytes(),
+            file_name: None,
+            content_type: Some(content_type),
+            source: PartSource::Bytes(content.into()),
+            sensitive: false,
+            raw: false,
+            headers: vec![],
+        }
+    }
+}
+
+/// Returns (content_type, body) of a multipart/mixed level
+fn nested(depth: usize, fan_out: usize) -> (String, Vec<u8>) {
+    let boundary = format!("nested-{}-{}", depth, random_boundary());
+    let child = (depth > 1).then(|| nested(depth - 1, fan_out));
+    let mut body = vec![];
+    for _ in 0..fan_out {
+        body.extend(format!("--{}\r\n", boundary).as_bytes());
+        match &child {
+            Some((content_type, content)) => {
+                body.extend(format!("Content-Type: {}\r\n\r\n", content_type).as_bytes());
+                body.extend(content);
+            }
+            None => body.extend(b"Content-Type: text/plain\r\n\r\nleaf"),
+        }
+        body.extend(b"\r\n");
+    }
+    body.extend(format!("--{}--\r\n", boundary).as_bytes());
+    (format!("multipart/mixed; boundary={}", boundary), body)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{MultiPartFormDataBuilder, MultiPartFormDataParser, Part};
+
+    fn count_leaves(content_type: &str, body: &[u8]) -> usize {
+        let parsed = MultiPartFormDataParser::parse(content_type, body).unwrap();
+        parsed
+            .parts
+            .iter()
+            .map(|part| match part.content_type.as_deref() {
+                Some(content_type) if content_type.starts_with("multipart/") => {
+                    count_leaves(content_type, &part.data)
+                }
+                _ => 1,
+            })
+            .sum()
+    }
+
+    #[test]
+    fn test_should_nest_multipart_with_fan_out() {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder.with_part(Part::nested_multipart("bomb", 4, 3));
+        let ((_, content_type), body) = multipart_form_data_builder.build();
+
+        assert_eq!(count_leaves(&content_type, &body), 81);
+    }
+}
@@ -0,0 +1,241 @@
+use std::fmt;
+
+use serde_json::Value;
+
+use crate::MultiPartFormDataBuilder;
+
+/// Violation of an OpenAPI multipart/form-data requestBody
+///
+/// Display renders one line, e.g. "field `lang` is required"
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OpenApiViolation {
+    /// Operation has no multipart/form-data requestBody schema
+    NoRequestBody,
+    /// Required property has no part
+    MissingField(String),
+    /// Part is not a schema property and additionalProperties is not true
+    UnexpectedField(String),
+    /// Part content type is not allowed by encoding contentType
+    ContentTypeMismatch {
+        name: String,
+        expected: String,
+        actual: Option<String>,
+    },
+}
+
+impl fmt::Display for OpenApiViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OpenApiViolation::NoRequestBody => {
+                write!(f, "operation has no multipart/form-data requestBody")
+            }
+            OpenApiViolation::MissingField(name) => write!(f, "field `{}` is required", name),
+            OpenApiViolation::UnexpectedField(name) => {
+                write!(f, "field `{}` is not in schema", name)
+            }
+            OpenApiViolation::ContentTypeMismatch {
+                name,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "field `{}` has content type {:?}, expected {:?}",
+                name, actual, expected
+            ),
+        }
+    }
+}
+
+impl MultiPartFormDataBuilder {
+    /// Validate parts against the multipart/form-data requestBody of an OpenAPI 3 operation
+    ///
+    /// spec is OpenAPI document as JSON, schema references to "#/components/schemas/..." are
+    /// resolved
+    ///
+    /// path is path template as in spec, e.g. "/users/{id}/avatar"
+    ///
+    /// method is operation method, e.g. "post", case insensitive
+    ///
+    /// Required properties must have a part, parts must be properties unless additionalProperties
+    /// is true, and content types must match encoding contentType, e.g. "image/png, image/*"
+    ///
+    /// Requires "openapi" feature
+    ///
+    /// Returns Err with every violation
+    pub fn validate_against_openapi(
+        &self,
+        spec: &Value,
+        path: &str,
+        method: &str,
+    ) -> Result<(), Vec<OpenApiViolation>> {
+        let media_type = &spec["paths"][path][method.to_ascii_lowercase()]["requestBody"]
+            ["content"]["multipart/form-data"];
+        let schema = resolve(spec, &media_type["schema"]);
+        if !schema.is_object() {
+            return Err(vec![OpenApiViolation::NoRequestBody]);
+        }
+        let names: Vec<String> = self
+            .parts
+            .iter()
+            .map(|part| String::from_utf8_lossy(&part.name).into_owned())
+            .collect();
+        let mut violations = vec![];
+        for required in schema["required"].as_array().into_iter().flatten() {
+            if let Some(required) = required.as_str() {
+                if !names.iter().any(|name| name == required) {
+                    violations.push(OpenApiViolation::MissingField(required.to_string()));
+                }
+            }
+        }
+        for (part, name) in self.parts.iter().zip(names) {
+            if schema["properties"].get(&name).is_none() {
+                if schema["additionalProperties"] != Value::Bool(true)
+                    && !violations.contains(&OpenApiViolation::UnexpectedField(name.clone()))
+                {
+                    violations.push(OpenApiViolation::UnexpectedField(name));
+                }
+                continue;
+            }
+            if let Some(expected) = media_type["encoding"][&name]["contentType"].as_str() {
+                if !content_type_matches(expected, part.content_type.as_deref()) {
+                    violations.push(OpenApiViolation::ContentTypeMismatch {
+                        name,
+                        expected: expected.to_string(),
+                        actual: part.content_type.clone(),
+                    });
+                }
+            }
+        }
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
+    }
+}
+
+/// Follow local "$ref" of schema
+fn resolve<'a>(spec: &'a Value, schema: &'a Value) -> &'a Value {
+    match schema["$ref"]
+        .as_str()
+        .and_then(|reference| reference.strip_prefix('#'))
+    {
+        Some(pointer) => spec.pointer(pointer).unwrap_or(&Value::Null),
+        None => schema,
+    }
+}
+
+/// Returns true when actual matches one of comma separated expected media ranges
+fn content_type_matches(expected: &str, actual: Option<&str>) -> bool {
+    let Some(actual) = actual else {
+        return false;
+    };
+    let actual = actual
+        .split(';')
+        .next()
+        .unwrap_or("")
+        .trim()
+        .to_ascii_lowercase();
+    expected.split(',').any(|range| {
+        let range = range.trim().to_ascii_lowercase();
+        match range.strip_suffix("/*") {
+            Some("*") => true,
+            Some(kind) => actual.split('/').next() == Some(kind),
+            None => range == actual,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn spec() -> Value {
+        json!({
+            "openapi": "3.0.3",
+            "paths": {
+                "/avatar": {
+                    "post": {
+                        "requestBody": {
+                            "content": {
+                                "multipart/form-data": {
+                                    "schema": { "$ref": "#/components/schemas/Avatar" },
+                                    "encoding": { "file": { "contentType": "image/png, image/jpeg" } }
+                                }
+                            }
+                        }
+                    }
+                }
+            },
+            "components": {
+                "schemas": {
+                    "Avatar": {
+                        "type": "object",
+                        "required": ["user_id", "file"],
+                        "properties": {
+                            "user_id": { "type": "string" },
+                            "file": { "type": "string", "format": "binary" }
+                        }
+                    }
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn test_should_accept_form_matching_openapi() {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder
+            .with_text("user_id", "1")
+            .with_file("tests/sample.png", "file", "image/png", "sample.png");
+
+        assert_eq!(
+            multipart_form_data_builder.validate_against_openapi(&spec(), "/avatar", "POST"),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_should_report_openapi_violations() {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder
+            .with_file("tests/sample.png", "file", "image/gif", "sample.gif")
+            .with_text("lang", "en");
+
+        let violations = multipart_form_data_builder
+            .validate_against_openapi(&spec(), "/avatar", "post")
+            .unwrap_err();
+
+        assert_eq!(
+            violations,
+            [
+                OpenApiViolation::MissingField("user_id".to_string()),
+                OpenApiViolation::ContentTypeMismatch {
+                    name: "file".to_string(),
+                    expected: "image/png, image/jpeg".to_string(),
+                    actual: Some("image/gif".to_string()),
+                },
+                OpenApiViolation::UnexpectedField("lang".to_string()),
+            ]
+        );
+        assert_eq!(violations[0].to_string(), "field `user_id` is required");
+        assert_eq!(
+            multipart_form_data_builder.validate_against_openapi(&spec(), "/avatar", "put"),
+            Err(vec![OpenApiViolation::NoRequestBody])
+        );
+    }
+
+    #[test]
+    fn test_should_match_content_type_ranges() {
+        assert!(content_type_matches("image/*", Some("image/png")));
+        assert!(content_type_matches("*/*", Some("text/plain")));
+        assert!(content_type_matches(
+            "text/plain",
+            Some("text/plain; charset=utf-8")
+        ));
+        assert!(!content_type_matches("image/*", Some("text/plain")));
+        assert!(!content_type_matches("image/png", None));
+    }
+}
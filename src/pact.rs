@@ -0,0 +1,111 @@
+use serde_json::{json, Map, Value};
+
+use crate::{mime::base64, MultiPartFormDataBuilder};
+
+impl MultiPartFormDataBuilder {
+    /// Build multipart/form-data as a Pact V4 interaction request, e.g. for consumer driven
+    /// contract tests sharing upload shapes with actix tests
+    ///
+    /// method is request method, e.g. "POST"
+    ///
+    /// path is request path, e.g. "/upload"
+    ///
+    /// Body is base64 encoded, matching rules accept any boundary in Content-Type and any
+    /// content of each part with the same content type
+    ///
+    /// Body rule keys use bracket notation, e.g. "$['meta data[x]']", so any part name is valid
+    ///
+    /// Requires "pact" feature
+    pub fn to_pact_request(&self, method: &str, path: &str) -> Value {
+        let ((header_name, header_value), body) = self.build();
+        let mut body_rules = Map::new();
+        for part in self.parts.iter() {
+            if let Some(content_type) = &part.content_type {
+                body_rules.insert(
+                    format!(
+                        "$['{}']",
+                        String::from_utf8_lossy(&part.name)
+                            .replace('\\', "\\\\")
+                            .replace('\'', "\\'")
+                    ),
+                    json!({
+                        "combine": "AND",
+                        "matchers": [{ "match": "contentType", "value": content_type }]
+                    }),
+                );
+            }
+        }
+        json!({
+            "method": method,
+            "path": path,
+            "headers": { header_name.clone(): [header_value] },
+            "body": {
+                "content": base64(&body),
+                "contentType": "multipart/form-data",
+                "encoded": "base64"
+            },
+            "matchingRules": {
+                "header": {
+                    header_name: {
+                        "combine": "AND",
+                        "matchers": [{
+                            "match": "regex",
+                            "regex": "multipart/form-data;\\s*boundary=.*"
+                        }]
+                    }
+                },
+                "body": body_rules
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_should_build_pact_request_with_matching_rules() {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder
+            .with_file("tests/sample.png", "sample", "image/png", "sample.png")
+            .with_text("name", "some_name")
+            .with_boundary("boundary");
+        let (_, body) = multipart_form_data_builder.build();
+
+        let request = multipart_form_data_builder.to_pact_request("PUT", "/upload");
+
+        assert_eq!(request["method"], "PUT");
+        assert_eq!(
+            request["headers"]["Content-Type"][0],
+            "multipart/form-data; boundary=boundary"
+        );
+        assert_eq!(request["body"]["content"], base64(&body));
+        assert_eq!(
+            request["matchingRules"]["header"]["Content-Type"]["matchers"][0]["match"],
+            "regex"
+        );
+        assert_eq!(
+            request["matchingRules"]["body"]["$['sample']"]["matchers"][0]["value"],
+            "image/png"
+        );
+        assert_eq!(
+            request["matchingRules"]["body"]["$['name']"]["matchers"][0]["value"],
+            "text/plain"
+        );
+    }
+
+    #[test]
+    fn test_should_quote_part_names_in_matching_rules() {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder
+            .with_text("meta data[x]", "some_value")
+            .with_text("it's", "some_value");
+
+        let request = multipart_form_data_builder.to_pact_request("POST", "/upload");
+
+        let body_rules = request["matchingRules"]["body"].as_object().unwrap();
+        assert!(body_rules.contains_key("$['meta data[x]']"));
+        assert!(body_rules.contains_key("$['it\\'s']"));
+    }
+}
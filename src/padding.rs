@@ -0,0 +1,136 @@
+use crate::{part::Part, MultiPartFormDataBuilder};
+
+/// Byte used to fill padding
+pub const PADDING_BYTE: u8 = b'x';
+
+/// Part grown by pad_to_total_size
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PaddingStrategy {
+    /// Append text part with this name, content is padding only
+    FillerPart(String),
+    /// Grow content of the last part with this name, file contents are read on build
+    GrowPart(String),
+}
+
+impl MultiPartFormDataBuilder {
+    /// Pad body to exactly total_size bytes on build, e.g. to test 413 handling at a limit
+    ///
+    /// Use limit + n as total_size for a body n bytes over a limit
+    ///
+    /// strategy is part grown with PADDING_BYTE, size is before body encoding
+    ///
+    /// Build panics when body without padding is larger than total_size, or when total_size is
+    /// skipped as the Content-Length header of the grown part gains a digit, e.g. 99 to 100
+    ///
+    /// Returns &mut MultiPartFormDataBuilder
+    pub fn pad_to_total_size(
+        &mut self,
+        total_size: usize,
+        strategy: PaddingStrategy,
+    ) -> &mut MultiPartFormDataBuilder {
+        self.total_size = Some((total_size, strategy));
+        self
+    }
+}
+
+/// Grow part chosen by strategy so body of parts with boundary is total_size bytes
+pub(crate) fn pad(
+    parts: &mut Vec<Part>,
+    boundary: &str,
+    total_size: usize,
+    strategy: &PaddingStrategy,
+) {
+    let index = match strategy {
+        PaddingStrategy::FillerPart(name) => {
+            parts.push(Part::text(name.clone(), ""));
+            parts.len() - 1
+        }
+        PaddingStrategy::GrowPart(name) => parts
+            .iter()
+            .rposition(|part| part.name == name.as_bytes())
+            .unwrap_or_else(|| panic!("no part named {:?} to pad", name)),
+    };
+    let body_len = parts
+        .iter()
+        .map(|part| part.encoded_len(boundary))
+        .sum::<usize>()
+        + boundary.len()
+        + 6;
+    assert!(
+        body_len <= total_size,
+        "body is already {} bytes, larger than {} bytes",
+        body_len,
+        total_size
+    );
+    let content_len = parts[index].content_len();
+    let padded_len =
+        |padding: usize| body_len + padding + digits(content_len + padding) - digits(content_len);
+    let mut padding = total_size - body_len;
+    while padding > 0 && padded_len(padding) > total_size {
+        padding -= 1;
+    }
+    assert_eq!(
+        padded_len(padding),
+        total_size,
+        "total size {} is not reachable, Content-Length of padded part gains a digit",
+        total_size
+    );
+    parts[index] = parts[index]
+        .clone()
+        .padded_to(content_len + padding, PADDING_BYTE);
+}
+
+/// Number of decimal digits of value
+fn digits(value: usize) -> usize {
+    value.checked_ilog10().unwrap_or(0) as usize + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MultiPartFormDataParser;
+
+    #[test]
+    fn test_should_pad_filler_part_to_total_size() {
+        for total_size in [400, 401, 1024, 10_000] {
+            let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+            multipart_form_data_builder
+                .with_text("name", "some_name")
+                .pad_to_total_size(
+                    total_size,
+                    PaddingStrategy::FillerPart("filler".to_string()),
+                );
+            let ((_, content_type), body) = multipart_form_data_builder.build();
+
+            assert_eq!(body.len(), total_size);
+            let parsed = MultiPartFormDataParser::parse(&content_type, &body).unwrap();
+            parsed.assert_part("name").assert_text("some_name");
+        }
+    }
+
+    #[test]
+    fn test_should_grow_file_part_to_total_size() {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder
+            .with_file("tests/sample.png", "sample", "image/png", "sample.png")
+            .pad_to_total_size(
+                64 * 1024 + 1,
+                PaddingStrategy::GrowPart("sample".to_string()),
+            );
+        let ((_, content_type), body) = multipart_form_data_builder.build();
+
+        assert_eq!(body.len(), 64 * 1024 + 1);
+        let parsed = MultiPartFormDataParser::parse(&content_type, &body).unwrap();
+        let sample = std::fs::read("tests/sample.png").unwrap();
+        assert!(parsed.parts[0].data.starts_with(&sample));
+    }
+
+    #[test]
+    #[should_panic(expected = "larger than")]
+    fn test_should_panic_when_body_is_larger_than_total_size() {
+        MultiPartFormDataBuilder::new()
+            .with_text("name", "some_name")
+            .pad_to_total_size(10, PaddingStrategy::FillerPart("filler".to_string()))
+            .build();
+    }
+}
@@ -0,0 +1,108 @@
+use std::{fs::File, io::Read, path::Path};
+
+use rayon::prelude::*;
+
+use crate::{
+    cache::FileCache,
+    part::{Part, PartSource},
+};
+
+/// Number of file parts, parts are read in parallel when more than one
+pub(crate) fn file_parts(parts: &[Part]) -> usize {
+    parts
+        .iter()
+        .filter(|part| matches!(part.source, PartSource::File(_)))
+        .count()
+}
+
+/// Write parts into empty body, file contents are read in parallel directly into body
+///
+/// Parts are written again sequentially when a file length changed after it was measured
+pub(crate) fn write_parts(
+    boundary: &str,
+    parts: &[Part],
+    cache: Option<&FileCache>,
+    body: &mut Vec<u8>,
+) {
+    let mut files: Vec<(usize, usize, &Path)> = vec![];
+    for part in parts.iter() {
+        match &part.source {
+            PartSource::File(path) => {
+                part.write_head(boundary, body);
+                let len = part.content_len();
+                files.push((body.len(), len, path));
+                body.resize(body.len() + len, 0);
+                body.extend_from_slice(b"\r\n");
+            }
+            _ => part.write(boundary, body, cache),
+        }
+    }
+
+    let mut contents = Vec::with_capacity(files.len());
+    let mut rest = body.as_mut_slice();
+    let mut written = 0;
+    for (offset, len, path) in files {
+        let (_, tail) = rest.split_at_mut(offset - written);
+        let (content, tail) = tail.split_at_mut(len);
+        contents.push((content, path));
+        rest = tail;
+        written = offset + len;
+    }
+
+    let measured = contents.into_par_iter().all(|(content, path)| match cache {
+        Some(cache) => {
+            let cached = cache.get(path);
+            let measured = cached.len() == content.len();
+            if measured {
+                content.copy_from_slice(&cached);
+            }
+            measured
+        }
+        None => {
+            let mut file = File::open(path).unwrap();
+            file.read_exact(content).is_ok() && file.read(&mut [0]).unwrap() == 0
+        }
+    });
+    if !measured {
+        body.clear();
+        for part in parts.iter() {
+            part.write(boundary, body, cache);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::part::write_body;
+
+    #[test]
+    fn test_should_write_same_body_as_sequential() {
+        let parts: Vec<Part> = (0..32)
+            .map(|index| {
+                if index % 3 == 0 {
+                    Part::text(format!("text{}", index), "some_name")
+                } else {
+                    Part::file(
+                        "tests/sample.png",
+                        format!("file{}", index),
+                        "image/png",
+                        "sample.png",
+                    )
+                }
+            })
+            .collect();
+
+        let mut expected = vec![];
+        for part in parts.iter() {
+            part.write("boundary", &mut expected, None);
+        }
+        expected.extend(b"--boundary--\r\n");
+
+        assert_eq!(write_body("boundary", &parts, None), expected);
+        assert_eq!(
+            write_body("boundary", &parts, Some(&FileCache::default())),
+            expected
+        );
+    }
+}
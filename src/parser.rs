@@ -0,0 +1,350 @@
+use std::fmt;
+
+/// Simple parser for multipart/form-data bodies
+///
+/// # Examples
+///
+/// ```
+/// use actix_multipart_test::{MultiPartFormDataBuilder, MultiPartFormDataParser};
+///
+/// let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+/// multipart_form_data_builder.with_text("name", "some_name");
+/// let ((_, content_type), body) = multipart_form_data_builder.build();
+///
+/// let parsed = MultiPartFormDataParser::parse(&content_type, &body).unwrap();
+/// assert_eq!(parsed.text("name"), Some("some_name"));
+/// ```
+pub struct MultiPartFormDataParser;
+
+/// Error returned by MultiPartFormDataParser
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// Content-Type is not multipart or has no boundary parameter
+    MissingBoundary,
+    /// Body does not contain the opening delimiter
+    MissingOpeningDelimiter,
+    /// Body ends before the closing delimiter
+    MissingClosingDelimiter,
+    /// Part headers are not terminated by an empty line
+    UnterminatedHeaders,
+    /// Part header line is not "name: value"
+    InvalidHeader(String),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::MissingBoundary => write!(f, "content type has no boundary"),
+            ParseError::MissingOpeningDelimiter => write!(f, "body has no opening delimiter"),
+            ParseError::MissingClosingDelimiter => write!(f, "body has no closing delimiter"),
+            ParseError::UnterminatedHeaders => write!(f, "part headers are not terminated"),
+            ParseError::InvalidHeader(line) => write!(f, "invalid part header: {}", line),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Part of a parsed multipart/form-data body
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedPart {
+    /// headers in received order
+    pub headers: Vec<(String, String)>,
+    /// name parameter of Content-Disposition
+    pub name: Option<String>,
+    /// filename parameter of Content-Disposition
+    pub file_name: Option<String>,
+    /// Content-Type header value
+    pub content_type: Option<String>,
+    /// part content
+    pub data: Vec<u8>,
+}
+
+impl ParsedPart {
+    /// Get header value by name, case insensitive
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header_name, _)| header_name.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Part content as text
+    ///
+    /// Returns None when content is not valid UTF-8
+    pub fn text(&self) -> Option<&str> {
+        std::str::from_utf8(&self.data).ok()
+    }
+}
+
+/// Parsed multipart/form-data body
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedMultiPartFormData {
+    /// boundary from Content-Type
+    pub boundary: String,
+    /// parts in received order
+    pub parts: Vec<ParsedPart>,
+}
+
+impl ParsedMultiPartFormData {
+    /// First part with name
+    pub fn part(&self, name: &str) -> Option<&ParsedPart> {
+        self.parts
+            .iter()
+            .find(|part| part.name.as_deref() == Some(name))
+    }
+
+    /// All parts with name
+    pub fn parts_named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a ParsedPart> {
+        self.parts
+            .iter()
+            .filter(move |part| part.name.as_deref() == Some(name))
+    }
+
+    /// Text content of first part with name
+    pub fn text(&self, name: &str) -> Option<&str> {
+        self.part(name).and_then(|part| part.text())
+    }
+}
+
+impl MultiPartFormDataParser {
+    /// Parse multipart body
+    ///
+    /// content_type is Content-Type header value, e.g. "multipart/form-data; boundary=..."
+    ///
+    /// body is binary data
+    ///
+    /// Returns ParsedMultiPartFormData or ParseError
+    pub fn parse(content_type: &str, body: &[u8]) -> Result<ParsedMultiPartFormData, ParseError> {
+        let boundary =
+            boundary_from_content_type(content_type).ok_or(ParseError::MissingBoundary)?;
+        let parts = parse_body(boundary.as_bytes(), body)?;
+        Ok(ParsedMultiPartFormData { boundary, parts })
+    }
+}
+
+/// Extract boundary parameter from a multipart Content-Type value
+pub(crate) fn boundary_from_content_type(content_type: &str) -> Option<String> {
+    let mut params = split_params(content_type);
+    let media_type = params.next()?;
+    if !media_type
+        .trim()
+        .to_ascii_lowercase()
+        .starts_with("multipart/")
+    {
+        return None;
+    }
+    params
+        .filter_map(|param| param.split_once('='))
+        .find(|(key, _)| key.trim().eq_ignore_ascii_case("boundary"))
+        .map(|(_, value)| unquote(value.trim()))
+        .filter(|boundary| !boundary.is_empty())
+}
+
+fn parse_body(boundary: &[u8], body: &[u8]) -> Result<Vec<ParsedPart>, ParseError> {
+    let mut delimiter = b"--".to_vec();
+    delimiter.extend_from_slice(boundary);
+    let mut close_delimiter = b"\r\n".to_vec();
+    close_delimiter.extend_from_slice(&delimiter);
+
+    let mut position = if body.starts_with(&delimiter) {
+        delimiter.len()
+    } else {
+        find(body, &close_delimiter, 0).ok_or(ParseError::MissingOpeningDelimiter)?
+            + close_delimiter.len()
+    };
+
+    let mut parts = vec![];
+    loop {
+        if body[position..].starts_with(b"--") {
+            return Ok(parts);
+        }
+        position = skip_transport_padding(body, position)?;
+
+        let (headers, content_start) = if body[position..].starts_with(b"\r\n") {
+            (vec![], position + 2)
+        } else {
+            let headers_end =
+                find(body, b"\r\n\r\n", position).ok_or(ParseError::UnterminatedHeaders)?;
+            (
+                parse_headers(&body[position..headers_end])?,
+                headers_end + 4,
+            )
+        };
+        let content_end = find(body, &close_delimiter, content_start)
+            .ok_or(ParseError::MissingClosingDelimiter)?;
+
+        parts.push(parsed_part(
+            headers,
+            body[content_start..content_end].to_vec(),
+        ));
+        position = content_end + close_delimiter.len();
+    }
+}
+
+fn skip_transport_padding(body: &[u8], mut position: usize) -> Result<usize, ParseError> {
+    while body
+        .get(position)
+        .is_some_and(|byte| *byte == b' ' || *byte == b'\t')
+    {
+        position += 1;
+    }
+    if body[position..].starts_with(b"\r\n") {
+        Ok(position + 2)
+    } else {
+        Err(ParseError::MissingClosingDelimiter)
+    }
+}
+
+fn parse_headers(raw: &[u8]) -> Result<Vec<(String, String)>, ParseError> {
+    String::from_utf8_lossy(raw)
+        .split("\r\n")
+        .map(|line| {
+            line.split_once(':')
+                .map(|(name, value)| (name.trim().to_string(), value.trim().to_string()))
+                .ok_or_else(|| ParseError::InvalidHeader(line.to_string()))
+        })
+        .collect()
+}
+
+fn parsed_part(headers: Vec<(String, String)>, data: Vec<u8>) -> ParsedPart {
+    let header = |name: &str| {
+        headers
+            .iter()
+            .find(|(header_name, _)| header_name.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    };
+    let mut name = None;
+    let mut file_name = None;
+    if let Some(disposition) = header("Content-Disposition") {
+        for (key, value) in split_params(disposition)
+            .skip(1)
+            .filter_map(|param| param.split_once('='))
+        {
+            match key.trim().to_ascii_lowercase().as_str() {
+                "name" => name = Some(unquote(value.trim())),
+                "filename" => file_name = Some(unquote(value.trim())),
+                _ => {}
+            }
+        }
+    }
+    let content_type = header("Content-Type").map(str::to_string);
+    ParsedPart {
+        headers,
+        name,
+        file_name,
+        content_type,
+        data,
+    }
+}
+
+/// Split header value on ';' outside quoted strings
+fn split_params(value: &str) -> impl Iterator<Item = &str> {
+    let mut params = vec![];
+    let mut start = 0;
+    let mut quoted = false;
+    let mut escaped = false;
+    for (index, char) in value.char_indices() {
+        match char {
+            _ if escaped => escaped = false,
+            '\\' if quoted => escaped = true,
+            '"' => quoted = !quoted,
+            ';' if !quoted => {
+                params.push(&value[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    params.push(&value[start..]);
+    params.into_iter()
+}
+
+/// Remove surrounding quotes and backslash escapes of a quoted string
+fn unquote(value: &str) -> String {
+    match value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+    {
+        Some(inner) => {
+            let mut unquoted = String::with_capacity(inner.len());
+            let mut chars = inner.chars();
+            while let Some(char) = chars.next() {
+                if char == '\\' {
+                    if let Some(escaped) = chars.next() {
+                        unquoted.push(escaped);
+                    }
+                } else {
+                    unquoted.push(char);
+                }
+            }
+            unquoted
+        }
+        None => value.to_string(),
+    }
+}
+
+pub(crate) fn find(haystack: &[u8], needle: &[u8], from: usize) -> Option<usize> {
+    if needle.is_empty() || from > haystack.len() {
+        return None;
+    }
+    haystack[from..]
+        .windows(needle.len())
+        .position(|window| window == needle)
+        .map(|position| position + from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MultiPartFormDataBuilder;
+
+    #[test]
+    fn test_should_parse_built_multipart_form() {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder.with_file(
+            "tests/sample.png",
+            "sample",
+            "image/png",
+            "sample.png",
+        );
+        multipart_form_data_builder.with_text("name", "some_name");
+        let ((_, content_type), body) = multipart_form_data_builder.build();
+
+        let parsed = MultiPartFormDataParser::parse(&content_type, &body).unwrap();
+
+        assert_eq!(parsed.parts.len(), 2);
+        let sample = parsed.part("sample").unwrap();
+        assert_eq!(sample.file_name.as_deref(), Some("sample.png"));
+        assert_eq!(sample.content_type.as_deref(), Some("image/png"));
+        assert_eq!(sample.data, std::fs::read("tests/sample.png").unwrap());
+        assert_eq!(parsed.text("name"), Some("some_name"));
+    }
+
+    #[test]
+    fn test_should_parse_quoted_boundary_and_preamble() {
+        let body = b"preamble\r\n--abc\r\nContent-Disposition: form-data; name=\"a;b\"\r\n\r\nvalue\r\n--abc--\r\n";
+        let parsed =
+            MultiPartFormDataParser::parse("multipart/form-data; boundary=\"abc\"", body).unwrap();
+
+        assert_eq!(parsed.boundary, "abc");
+        assert_eq!(parsed.text("a;b"), Some("value"));
+    }
+
+    #[test]
+    fn test_should_fail_without_boundary() {
+        assert_eq!(
+            MultiPartFormDataParser::parse("text/plain", b""),
+            Err(ParseError::MissingBoundary)
+        );
+    }
+
+    #[test]
+    fn test_should_fail_without_closing_delimiter() {
+        let body = b"--abc\r\nContent-Disposition: form-data; name=\"a\"\r\n\r\nvalue";
+        assert_eq!(
+            MultiPartFormDataParser::parse("multipart/form-data; boundary=abc", body),
+            Err(ParseError::MissingClosingDelimiter)
+        );
+    }
+}
@@ -0,0 +1,901 @@
+use std::{
+    borrow::Cow,
+    collections::HashSet,
+    fmt,
+    fs::File,
+    io::{self, Read, Write},
+    path::PathBuf,
+    sync::Arc,
+};
+
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+use std::path::Path;
+
+use bytes::Bytes;
+
+use crate::{cache::FileCache, lazy::LazyContent};
+
+/// Content type of file parts created without one
+pub const DEFAULT_CONTENT_TYPE: &str = "application/octet-stream";
+
+/// Content source of a part, in memory contents are shared without copying
+#[derive(Clone)]
+pub(crate) enum PartSource {
+    #[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), allow(dead_code))]
+    File(PathBuf),
+    Bytes(Bytes),
+    Lazy(Arc<LazyContent>),
+}
+
+/// Part of multipart/form-data, added with MultiPartFormDataBuilder::with_part
+///
+/// Cloning a part shares its content
+///
+/// # Examples
+///
+/// ```
+/// use actix_multipart_test::{MultiPartFormDataBuilder, Part};
+///
+/// let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+/// multipart_form_data_builder
+///     .with_part(Part::text("name", "some_name"))
+///     .with_repeated_parts(&Part::text("tag", "value"), 10_000);
+/// let (header, body) = multipart_form_data_builder.build();
+/// ```
+#[derive(Clone)]
+pub struct Part {
+    pub(crate) name: Vec<u8>,
+    pub(crate) file_name: Option<Vec<u8>>,
+    pub(crate) content_type: Option<String>,
+    pub(crate) source: PartSource,
+    pub(crate) sensitive: bool,
+    pub(crate) raw: bool,
+    pub(crate) headers: Vec<(String, String)>,
+}
+
+impl Part {
+    /// Create text part
+    ///
+    /// name is form name
+    ///
+    /// value is form value, content type is "text/plain"
+    pub fn text(name: impl Into<String>, value: impl Into<String>) -> Part {
+        Part {
+            name: name.into().into_bytes(),
+            file_name: None,
+            content_type: Some("text/plain".to_string()),
+            source: PartSource::Bytes(value.into().into()),
+            sensitive: false,
+            raw: false,
+            headers: vec![],
+        }
+    }
+
+    /// Create text part without copying the value
+    ///
+    /// name is form name
+    ///
+    /// value is form value, borrowed static strings are never copied, content type is "text/plain"
+    pub fn static_text(name: impl Into<String>, value: impl Into<Cow<'static, str>>) -> Part {
+        let source = match value.into() {
+            Cow::Borrowed(value) => Bytes::from_static(value.as_bytes()),
+            Cow::Owned(value) => Bytes::from(value),
+        };
+        Part {
+            name: name.into().into_bytes(),
+            file_name: None,
+            content_type: Some("text/plain".to_string()),
+            source: PartSource::Bytes(source),
+            sensitive: false,
+            raw: false,
+            headers: vec![],
+        }
+    }
+
+    /// Create text part with the value of an environment variable, read on first build
+    ///
+    /// name is form name
+    ///
+    /// env_var is environment variable name, e.g. a credential or a per environment id
+    ///
+    /// default is the value when env_var is not set, panics at build when env_var is not set and
+    /// there is no default or when its value is not valid unicode
+    pub fn env_text(
+        name: impl Into<String>,
+        env_var: impl Into<String>,
+        default: Option<&str>,
+    ) -> Part {
+        let env_var = env_var.into();
+        let default = default.map(str::to_string);
+        Part {
+            source: PartSource::Lazy(Arc::new(LazyContent::new(move || {
+                match (std::env::var(&env_var), default) {
+                    (Ok(value), _) => value.into(),
+                    (Err(std::env::VarError::NotPresent), Some(default)) => default.into(),
+                    (Err(error), _) => panic!("environment variable `{}`: {}", env_var, error),
+                }
+            }))),
+            ..Part::text(name, "")
+        }
+    }
+
+    /// Create file part from in memory content, content is shared without copying
+    ///
+    /// name is form name
+    ///
+    /// file_name is file name
+    ///
+    /// content_type is file content type
+    ///
+    /// content is file content, e.g. Bytes, Vec<u8> or &'static [u8]
+    pub fn bytes(
+        name: impl Into<String>,
+        file_name: impl Into<String>,
+        content_type: impl Into<String>,
+        content: impl Into<Bytes>,
+    ) -> Part {
+        Part {
+            name: name.into().into_bytes(),
+            file_name: Some(file_name.into().into_bytes()),
+            content_type: Some(content_type.into()),
+            source: PartSource::Bytes(content.into()),
+            sensitive: false,
+            raw: false,
+            headers: vec![],
+        }
+    }
+
+    /// Create file part from in memory content with content type "application/octet-stream"
+    ///
+    /// name is form name
+    ///
+    /// file_name is file name
+    ///
+    /// content is file content, e.g. Bytes, Vec<u8> or &'static [u8]
+    pub fn bytes_default_type(
+        name: impl Into<String>,
+        file_name: impl Into<String>,
+        content: impl Into<Bytes>,
+    ) -> Part {
+        Part::bytes(name, file_name, DEFAULT_CONTENT_TYPE, content)
+    }
+
+    /// Create file part whose content is produced on first build, e.g. an expensive fixture
+    ///
+    /// name is form name
+    ///
+    /// file_name is file name
+    ///
+    /// content_type is file content type
+    ///
+    /// init produces file content, e.g. Vec<u8> or Bytes, called at most once and never when the
+    /// part is not built, clones of the part share the produced content
+    pub fn lazy<C: Into<Bytes>>(
+        name: impl Into<String>,
+        file_name: impl Into<String>,
+        content_type: impl Into<String>,
+        init: impl FnOnce() -> C + Send + 'static,
+    ) -> Part {
+        Part {
+            name: name.into().into_bytes(),
+            file_name: Some(file_name.into().into_bytes()),
+            content_type: Some(content_type.into()),
+            source: PartSource::Lazy(Arc::new(LazyContent::new(move || init().into()))),
+            sensitive: false,
+            raw: false,
+            headers: vec![],
+        }
+    }
+
+    /// Create lazy file part, see lazy, with content type "application/octet-stream"
+    ///
+    /// name is form name
+    ///
+    /// file_name is file name
+    ///
+    /// init produces file content, called at most once and never when the part is not built
+    pub fn lazy_default_type<C: Into<Bytes>>(
+        name: impl Into<String>,
+        file_name: impl Into<String>,
+        init: impl FnOnce() -> C + Send + 'static,
+    ) -> Part {
+        Part::lazy(name, file_name, DEFAULT_CONTENT_TYPE, init)
+    }
+
+    /// Create file part sent by browsers for an empty file input
+    ///
+    /// name is form name
+    ///
+    /// file name is "", content type is "application/octet-stream" and content is empty
+    pub fn empty_file(name: impl Into<String>) -> Part {
+        Part::bytes(name, "", DEFAULT_CONTENT_TYPE, Bytes::new())
+    }
+
+    /// Create file part from static content, e.g. include_bytes!, content is never copied until build
+    ///
+    /// name is form name
+    ///
+    /// file_name is file name
+    ///
+    /// content_type is file content type
+    ///
+    /// content is file content
+    pub fn static_file(
+        name: impl Into<String>,
+        file_name: impl Into<String>,
+        content_type: impl Into<String>,
+        content: &'static [u8],
+    ) -> Part {
+        Part::bytes(name, file_name, content_type, Bytes::from_static(content))
+    }
+
+    /// Create text part with raw bytes
+    ///
+    /// name is form name, written as is, may be invalid UTF-8
+    ///
+    /// value is form value, written as is, may be invalid UTF-8
+    pub fn raw_text(name: impl Into<Vec<u8>>, value: impl Into<Vec<u8>>) -> Part {
+        Part {
+            name: name.into(),
+            file_name: None,
+            content_type: Some("text/plain".to_string()),
+            source: PartSource::Bytes(value.into().into()),
+            sensitive: false,
+            raw: true,
+            headers: vec![],
+        }
+    }
+
+    /// Create file part, file is read on build
+    ///
+    /// path is file path
+    ///
+    /// name is form name
+    ///
+    /// content_type is file content type
+    ///
+    /// file_name is file name
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    pub fn file(
+        path: impl AsRef<Path>,
+        name: impl Into<String>,
+        content_type: impl Into<String>,
+        file_name: impl Into<String>,
+    ) -> Part {
+        Part {
+            raw: false,
+            ..Part::raw_file(
+                path,
+                name.into().into_bytes(),
+                content_type,
+                file_name.into().into_bytes(),
+            )
+        }
+    }
+
+    /// Create file part with content type "application/octet-stream", file is read on build
+    ///
+    /// path is file path
+    ///
+    /// name is form name
+    ///
+    /// file_name is file name
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    pub fn file_default_type(
+        path: impl AsRef<Path>,
+        name: impl Into<String>,
+        file_name: impl Into<String>,
+    ) -> Part {
+        Part::file(path, name, DEFAULT_CONTENT_TYPE, file_name)
+    }
+
+    /// Create file part with raw bytes name and file name, file is read on build
+    ///
+    /// path is file path
+    ///
+    /// name is form name, written as is, may be invalid UTF-8
+    ///
+    /// content_type is file content type
+    ///
+    /// file_name is file name, written as is, may be invalid UTF-8
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    pub fn raw_file(
+        path: impl AsRef<Path>,
+        name: impl Into<Vec<u8>>,
+        content_type: impl Into<String>,
+        file_name: impl Into<Vec<u8>>,
+    ) -> Part {
+        Part {
+            name: name.into(),
+            file_name: Some(file_name.into()),
+            content_type: Some(content_type.into()),
+            source: PartSource::File(path.as_ref().to_path_buf()),
+            sensitive: false,
+            raw: true,
+            headers: vec![],
+        }
+    }
+
+    /// Memory map file content instead of reading it on each build
+    ///
+    /// File is mapped once, repeated builds and clones of the part share the mapping
+    ///
+    /// File must not be modified while the part is alive, parts not created from a file are unchanged
+    #[cfg(feature = "memmap2")]
+    pub fn memory_mapped(mut self) -> Part {
+        if let PartSource::File(path) = &self.source {
+            let file = File::open(path).unwrap();
+            // SAFETY: fixtures are not modified while tests use them, as documented above
+            let mmap = unsafe { memmap2::Mmap::map(&file) }.unwrap();
+            self.source = PartSource::Bytes(Bytes::from_owner(mmap));
+        }
+        self
+    }
+
+    /// Write name and file name as is, without escaping '"' and '\\', e.g. for broken payloads or
+    /// Windows paths like "C:\\Users\\me\\file.png" sent by old Internet Explorer clients
+    ///
+    /// Parts created with raw_text and raw_file are always written as is
+    pub fn raw(mut self) -> Part {
+        self.raw = true;
+        self
+    }
+
+    /// Replace content type
+    pub fn with_content_type(mut self, content_type: impl Into<String>) -> Part {
+        self.content_type = Some(content_type.into());
+        self
+    }
+
+    /// Omit Content-Type header, e.g. to test default type fallback of servers
+    pub fn without_content_type(mut self) -> Part {
+        self.content_type = None;
+        self
+    }
+
+    /// Mark part as sensitive, e.g. tokens or personal data
+    ///
+    /// Sensitive values are masked in Debug output and snapshot strings, the body is unchanged
+    ///
+    /// tracing events never include values
+    pub fn sensitive(mut self) -> Part {
+        self.sensitive = true;
+        self
+    }
+
+    /// Apply Unicode normalization to file name, e.g. Nfd to send names like macOS clients
+    ///
+    /// Requires "unicode-normalization" feature
+    #[cfg(feature = "unicode-normalization")]
+    pub fn with_file_name_normalization(
+        mut self,
+        normalization: crate::encoding::FileNameNormalization,
+    ) -> Part {
+        self.file_name = self
+            .file_name
+            .map(|file_name| normalization.normalize(&file_name));
+        self
+    }
+
+    /// Add part header, written after Content-Type, e.g. ("Content-Language", "en")
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Part {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Gzip content and add a "Content-Encoding: gzip" part header, e.g. for compressed CSV parts
+    ///
+    /// File contents are read now, Content-Length is the compressed length
+    pub fn gzip(mut self) -> Part {
+        self.source = PartSource::Bytes(crate::encoding::gzip(&self.read()).into());
+        self.with_header("Content-Encoding", "gzip")
+    }
+
+    /// Encode content as base64 in lines of 76 characters and add a
+    /// "Content-Transfer-Encoding: base64" part header, e.g. to simulate legacy clients
+    ///
+    /// RFC 7578 deprecates Content-Transfer-Encoding, see ComplianceMode
+    ///
+    /// File contents are read now, Content-Length is the encoded length
+    pub fn base64(mut self) -> Part {
+        self.source = PartSource::Bytes(crate::mime::base64_lines(&self.read()).into());
+        self.with_header("Content-Transfer-Encoding", "base64")
+    }
+
+    /// Prefix content with a UTF-8 byte order mark, e.g. to test deserializers of JSON parts
+    ///
+    /// File contents are read now
+    pub fn with_bom(mut self) -> Part {
+        let mut content = Vec::with_capacity(self.content_len() + 3);
+        content.extend_from_slice(b"\xef\xbb\xbf");
+        self.read_into(&mut content, None);
+        self.source = PartSource::Bytes(content.into());
+        self
+    }
+
+    /// Pad content to exactly len bytes with fill, e.g. to test a field size limit at its boundary
+    ///
+    /// File contents are read now, panics when content is longer than len
+    pub fn padded_to(mut self, len: usize, fill: u8) -> Part {
+        let mut content = self.read();
+        assert!(
+            content.len() <= len,
+            "part content is {} bytes, longer than {} bytes",
+            content.len(),
+            len
+        );
+        content.resize(len, fill);
+        self.source = PartSource::Bytes(content.into());
+        self
+    }
+
+    /// Content length in bytes, files are measured with metadata
+    /// Content length when known without reading, None for missing files and lazy content not
+    /// yet produced
+    pub(crate) fn known_len(&self) -> Option<usize> {
+        match &self.source {
+            PartSource::File(path) => std::fs::metadata(path)
+                .ok()
+                .map(|metadata| metadata.len() as usize),
+            PartSource::Bytes(bytes) => Some(bytes.len()),
+            PartSource::Lazy(lazy) => lazy.get_if_produced().map(|bytes| bytes.len()),
+        }
+    }
+
+    pub(crate) fn content_len(&self) -> usize {
+        match &self.source {
+            PartSource::File(path) => std::fs::metadata(path).unwrap().len() as usize,
+            PartSource::Bytes(bytes) => bytes.len(),
+            PartSource::Lazy(lazy) => lazy.get().len(),
+        }
+    }
+
+    /// Append part content to out, file contents come from cache when given
+    pub(crate) fn read_into(&self, out: &mut Vec<u8>, cache: Option<&FileCache>) {
+        match &self.source {
+            PartSource::File(path) => match cache {
+                Some(cache) => out.extend_from_slice(&cache.get(path)),
+                None => {
+                    File::open(path).unwrap().read_to_end(out).unwrap();
+                }
+            },
+            PartSource::Bytes(bytes) => out.extend_from_slice(bytes),
+            PartSource::Lazy(lazy) => out.extend_from_slice(lazy.get()),
+        }
+    }
+
+    /// Read part content
+    pub(crate) fn read(&self) -> Vec<u8> {
+        let mut content = Vec::with_capacity(self.content_len());
+        self.read_into(&mut content, None);
+        content
+    }
+
+    fn write_disposition(&self, out: &mut impl Write) -> io::Result<()> {
+        out.write_all(b"form-data; name=")?;
+        self.write_quoted(&self.name, out)?;
+        if let Some(file_name) = &self.file_name {
+            out.write_all(b"; filename=")?;
+            self.write_quoted(file_name, out)?;
+        }
+        Ok(())
+    }
+
+    /// Write value as quoted-string, '"' and '\\' are escaped unless part is raw
+    fn write_quoted(&self, value: &[u8], out: &mut impl Write) -> io::Result<()> {
+        out.write_all(b"\"")?;
+        if self.raw {
+            out.write_all(value)?;
+        } else {
+            for chunk in value.split_inclusive(|byte| matches!(byte, b'"' | b'\\')) {
+                match chunk.split_last() {
+                    Some((last @ (b'"' | b'\\'), rest)) => {
+                        out.write_all(rest)?;
+                        out.write_all(&[b'\\', *last])?;
+                    }
+                    _ => out.write_all(chunk)?,
+                }
+            }
+        }
+        out.write_all(b"\"")
+    }
+
+    fn write_headers(&self, content_len: usize, out: &mut impl Write) -> io::Result<()> {
+        out.write_all(b"Content-Disposition: ")?;
+        self.write_disposition(out)?;
+        out.write_all(b"\r\n")?;
+        if let Some(content_type) = &self.content_type {
+            write!(out, "Content-Type: {}\r\n", content_type)?;
+        }
+        for (name, value) in self.headers.iter() {
+            write!(out, "{}: {}\r\n", name, value)?;
+        }
+        write!(out, "Content-Length: {}\r\n", content_len)
+    }
+
+    /// Length of delimiter, headers, content and trailing CRLF
+    pub(crate) fn encoded_len(&self, boundary: &str) -> usize {
+        let content_len = self.content_len();
+        let mut counter = Counter(0);
+        self.write_headers(content_len, &mut counter).unwrap();
+        boundary.len() + 4 + counter.0 + 2 + content_len + 2
+    }
+
+    /// Append delimiter, headers and blank line to out
+    pub(crate) fn write_head(&self, boundary: &str, out: &mut Vec<u8>) {
+        out.extend_from_slice(b"--");
+        out.extend_from_slice(boundary.as_bytes());
+        out.extend_from_slice(b"\r\n");
+        self.write_headers(self.content_len(), out).unwrap();
+        out.extend_from_slice(b"\r\n");
+    }
+
+    /// Append delimiter, headers, content and trailing CRLF to out
+    pub(crate) fn write(&self, boundary: &str, out: &mut Vec<u8>, cache: Option<&FileCache>) {
+        self.write_head(boundary, out);
+        self.read_into(out, cache);
+        out.extend_from_slice(b"\r\n");
+    }
+
+    /// Encode part headers and content
+    pub(crate) fn encode(&self) -> EncodedPart {
+        let mut disposition = vec![];
+        self.write_disposition(&mut disposition).unwrap();
+        let content = self.read();
+        let mut headers = vec![(b"Content-Disposition".to_vec(), disposition)];
+        if let Some(content_type) = &self.content_type {
+            headers.push(header("Content-Type", content_type));
+        }
+        for (name, value) in self.headers.iter() {
+            headers.push(header(name, value));
+        }
+        headers.push(header("Content-Length", content.len().to_string()));
+        EncodedPart {
+            headers,
+            blank_line: true,
+            content,
+        }
+    }
+}
+
+impl fmt::Debug for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let content = match &self.source {
+            // never reads files or forces lazy content
+            _ if self.sensitive => match self.known_len() {
+                Some(len) => format!("<redacted {} bytes>", len),
+                None => "<redacted>".to_string(),
+            },
+            PartSource::File(path) => format!("<file {}>", path.display()),
+            PartSource::Bytes(bytes) => match std::str::from_utf8(bytes) {
+                Ok(text) => format!("{:?}", text),
+                Err(_) => format!("<{} bytes>", bytes.len()),
+            },
+            PartSource::Lazy(lazy) => match lazy.get_if_produced() {
+                Some(bytes) => format!("<{} bytes>", bytes.len()),
+                None => "<not yet produced>".to_string(),
+            },
+        };
+        f.debug_struct("Part")
+            .field("name", &String::from_utf8_lossy(&self.name))
+            .field(
+                "file_name",
+                &self
+                    .file_name
+                    .as_ref()
+                    .map(|file_name| String::from_utf8_lossy(file_name)),
+            )
+            .field("content_type", &self.content_type)
+            .field("content", &format_args!("{}", content))
+            .finish()
+    }
+}
+
+/// Write multipart body of parts, buffer is allocated once with the exact body length
+#[cfg(test)]
+pub(crate) fn write_body(boundary: &str, parts: &[Part], cache: Option<&FileCache>) -> Vec<u8> {
+    let mut body = vec![];
+    write_body_into(boundary, parts, cache, &mut body);
+    body
+}
+
+/// Clear body and write multipart body of parts into it, reserving the exact body length
+pub(crate) fn write_body_into(
+    boundary: &str,
+    parts: &[Part],
+    cache: Option<&FileCache>,
+    body: &mut Vec<u8>,
+) {
+    let len = parts
+        .iter()
+        .map(|part| part.encoded_len(boundary))
+        .sum::<usize>()
+        + boundary.len()
+        + 6;
+    #[cfg(feature = "tracing")]
+    let _span = crate::trace::build_span(boundary, parts);
+    let build_cache;
+    let cache = match cache {
+        None if has_repeated_files(parts) => {
+            build_cache = FileCache::default();
+            Some(&build_cache)
+        }
+        cache => cache,
+    };
+    body.clear();
+    body.reserve_exact(len);
+    #[cfg(feature = "rayon")]
+    if crate::parallel::file_parts(parts) > 1 {
+        crate::parallel::write_parts(boundary, parts, cache, body);
+    } else {
+        for part in parts.iter() {
+            part.write(boundary, body, cache);
+        }
+    }
+    #[cfg(not(feature = "rayon"))]
+    for part in parts.iter() {
+        part.write(boundary, body, cache);
+    }
+    body.extend_from_slice(b"--");
+    body.extend_from_slice(boundary.as_bytes());
+    body.extend_from_slice(b"--\r\n");
+    #[cfg(feature = "tracing")]
+    tracing::debug!(size = body.len(), "built multipart body");
+}
+
+/// Returns true when a file path is used by several parts, it is then read once per build
+fn has_repeated_files(parts: &[Part]) -> bool {
+    let mut paths = HashSet::new();
+    parts.iter().any(|part| match &part.source {
+        PartSource::File(path) => !paths.insert(path),
+        PartSource::Bytes(_) | PartSource::Lazy(_) => false,
+    })
+}
+
+/// Writer counting written bytes
+struct Counter(usize);
+
+impl Write for Counter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0 += buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn header(name: &str, value: impl AsRef<str>) -> (Vec<u8>, Vec<u8>) {
+    (name.as_bytes().to_vec(), value.as_ref().as_bytes().to_vec())
+}
+
+/// Part ready to be written, headers and content are raw bytes
+pub(crate) struct EncodedPart {
+    pub(crate) headers: Vec<(Vec<u8>, Vec<u8>)>,
+    pub(crate) blank_line: bool,
+    pub(crate) content: Vec<u8>,
+}
+
+/// Body ready to be written
+///
+/// delimiter is written before each part and terminator after the last one
+pub(crate) struct EncodedBody {
+    pub(crate) boundary: String,
+    pub(crate) preamble: Vec<u8>,
+    pub(crate) delimiter: Vec<u8>,
+    pub(crate) parts: Vec<EncodedPart>,
+    pub(crate) terminator: Vec<u8>,
+}
+
+impl EncodedBody {
+    /// Encode parts with boundary
+    pub(crate) fn new<'a>(boundary: &str, parts: impl Iterator<Item = &'a Part>) -> EncodedBody {
+        EncodedBody {
+            boundary: boundary.to_string(),
+            preamble: vec![],
+            delimiter: format!("--{}\r\n", boundary).into_bytes(),
+            parts: parts.map(Part::encode).collect(),
+            terminator: format!("--{}--\r\n", boundary).into_bytes(),
+        }
+    }
+
+    /// Write body bytes
+    pub(crate) fn write(&self) -> Vec<u8> {
+        let mut body = self.preamble.clone();
+        for part in self.parts.iter() {
+            body.extend(&self.delimiter);
+            for (name, value) in part.headers.iter() {
+                body.extend(name);
+                body.extend(b": ");
+                body.extend(value);
+                body.extend(b"\r\n");
+            }
+            if part.blank_line {
+                body.extend(b"\r\n");
+            }
+            body.extend(&part.content);
+            body.extend(b"\r\n");
+        }
+        body.extend(&self.terminator);
+        body
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_should_escape_quoted_strings_unless_raw() {
+        let part = Part::bytes(r#"a"b"#, r#"c:\d".txt"#, "text/plain", "x");
+        let mut head = vec![];
+        part.write_head("boundary", &mut head);
+        let mut raw_head = vec![];
+        part.raw().write_head("boundary", &mut raw_head);
+
+        assert!(String::from_utf8(head)
+            .unwrap()
+            .contains(r#"name="a\"b"; filename="c:\\d\".txt""#));
+        assert!(String::from_utf8(raw_head)
+            .unwrap()
+            .contains(r#"name="a"b"; filename="c:\d".txt""#));
+    }
+
+    #[test]
+    fn test_should_detect_repeated_files() {
+        let front = Part::file("tests/sample.png", "front", "image/png", "front.png");
+        let back = Part::file("tests/sample.png", "back", "image/png", "back.png");
+        let parts = vec![front.clone(), Part::text("name", "some_name"), back];
+
+        assert!(has_repeated_files(&parts));
+        assert!(!has_repeated_files(&parts[..2]));
+        let body = write_body("boundary", &parts, None);
+        let sample = std::fs::read("tests/sample.png").unwrap();
+        assert_eq!(
+            body.windows(sample.len())
+                .filter(|window| *window == sample.as_slice())
+                .count(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_should_omit_content_type() {
+        let part = Part::bytes("doc", "doc.bin", "application/pdf", "x").without_content_type();
+        let mut head = vec![];
+        part.write_head("boundary", &mut head);
+
+        assert_eq!(
+            head,
+            b"--boundary\r\nContent-Disposition: form-data; name=\"doc\"; filename=\"doc.bin\"\r\nContent-Length: 1\r\n\r\n"
+        );
+        assert_eq!(part.encoded_len("boundary"), head.len() + 3);
+    }
+
+    #[test]
+    fn test_should_mask_sensitive_part_in_debug() {
+        assert_eq!(
+            format!("{:?}", Part::text("token", "secret").sensitive()),
+            "Part { name: \"token\", file_name: None, content_type: Some(\"text/plain\"), content: <redacted 6 bytes> }"
+        );
+        assert!(format!("{:?}", Part::text("token", "secret")).contains("content: \"secret\""));
+    }
+
+    #[test]
+    fn test_should_mask_sensitive_part_in_debug_without_reading_content() {
+        let lazy = Part::lazy(
+            "doc",
+            "doc.bin",
+            "application/octet-stream",
+            || -> Vec<u8> { panic!("lazy content is produced") },
+        )
+        .sensitive();
+        let missing =
+            Part::file("tests/missing.png", "sample", "image/png", "missing.png").sensitive();
+
+        assert!(format!("{:?}", lazy).contains("content: <redacted> }"));
+        assert!(format!("{:?}", missing).contains("content: <redacted> }"));
+    }
+
+    #[test]
+    fn test_should_allocate_exact_body_length() {
+        let parts = vec![
+            Part::file("tests/sample.png", "sample", "image/png", "sample.png"),
+            Part::text("name", "some_name"),
+            Part::raw_text(b"caf\xe9".to_vec(), b"\xff".to_vec()),
+            Part::text("notes", "some_notes")
+                .with_header("Content-Language", "en")
+                .gzip(),
+        ];
+        let boundary = "boundary";
+
+        let body = write_body(boundary, &parts, None);
+
+        assert_eq!(body.len(), body.capacity());
+        assert_eq!(body, EncodedBody::new(boundary, parts.iter()).write());
+    }
+
+    #[test]
+    fn test_should_keep_static_file_content_without_copying() {
+        static CONTENT: &[u8] = include_bytes!("../tests/sample.png");
+        let part = Part::static_file("sample", "sample.png", "image/png", CONTENT);
+        let cloned = part.clone();
+
+        for part in [part, cloned] {
+            match &part.source {
+                PartSource::Bytes(bytes) => assert_eq!(bytes.as_ptr(), CONTENT.as_ptr()),
+                _ => panic!("static file part is not in memory"),
+            }
+        }
+    }
+
+    #[cfg(feature = "memmap2")]
+    #[test]
+    fn test_should_write_memory_mapped_file() {
+        let parts = vec![
+            Part::file("tests/sample.png", "sample", "image/png", "sample.png").memory_mapped(),
+        ];
+        let expected = write_body(
+            "boundary",
+            &[Part::file(
+                "tests/sample.png",
+                "sample",
+                "image/png",
+                "sample.png",
+            )],
+            None,
+        );
+
+        assert!(matches!(parts[0].source, PartSource::Bytes(_)));
+        assert_eq!(write_body("boundary", &parts, None), expected);
+    }
+
+    #[test]
+    fn test_should_pad_part_content_to_len() {
+        let text = Part::text("name", "some_name").padded_to(16, b'-');
+        let file = Part::file("tests/sample.png", "sample", "image/png", "sample.png")
+            .padded_to(64 * 1024, 0);
+
+        assert_eq!(text.read(), b"some_name-------");
+        let content = file.read();
+        assert_eq!(content.len(), 64 * 1024);
+        assert!(content.starts_with(&std::fs::read("tests/sample.png").unwrap()));
+        assert_eq!(file.file_name.as_deref(), Some(&b"sample.png"[..]));
+    }
+
+    #[test]
+    #[should_panic(expected = "longer than")]
+    fn test_should_panic_when_part_content_is_longer_than_len() {
+        Part::text("name", "some_name").padded_to(4, b'-');
+    }
+
+    #[test]
+    fn test_should_prefix_content_with_bom() {
+        let part = Part::text("metadata", "{}")
+            .with_content_type("application/json")
+            .with_bom();
+
+        assert_eq!(part.read(), b"\xef\xbb\xbf{}");
+        assert_eq!(part.content_type.as_deref(), Some("application/json"));
+    }
+
+    #[test]
+    fn test_should_gzip_part_content_with_header() {
+        use std::io::Read;
+
+        let part = Part::bytes("import", "import.csv", "text/csv", "id,name\r\n1,a\r\n").gzip();
+        let body = write_body("boundary", &[part], None);
+
+        let parsed =
+            crate::MultiPartFormDataParser::parse("multipart/form-data; boundary=boundary", &body)
+                .unwrap();
+        let part = parsed.assert_part("import").assert_content_type("text/csv");
+        assert_eq!(part.header("Content-Encoding"), Some("gzip"));
+        let mut decoded = String::new();
+        flate2::read::GzDecoder::new(part.data.as_slice())
+            .read_to_string(&mut decoded)
+            .unwrap();
+        assert_eq!(decoded, "id,name\r\n1,a\r\n");
+    }
+}
@@ -0,0 +1,606 @@
+//! Ready-made builders for common upload shapes
+//!
+//! Each preset returns a pre-populated MultiPartFormDataBuilder, more parts can be added before build
+//!
+//! # Examples
+//!
+//! ```
+//! use actix_multipart_test::presets;
+//!
+//! let (header, body) = presets::avatar_upload().with_text("user_id", "1").build();
+//! ```
+
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+use std::path::Path;
+
+use crate::{MultiPartFormDataBuilder, Part, DEFAULT_CONTENT_TYPE};
+
+/// Transparent 1x1 PNG image
+pub const AVATAR_PNG: &[u8] = b"\x89\x50\x4e\x47\x0d\x0a\x1a\x0a\x00\x00\x00\x0d\x49\x48\x44\x52\x00\x00\x00\x01\x00\x00\x00\x01\x08\x06\x00\x00\x00\x1f\x15\xc4\x89\x00\x00\x00\x0b\x49\x44\x41\x54\x78\x9c\x63\x60\x00\x02\x00\x00\x05\x00\x01\x7a\x5e\xab\x3f\x00\x00\x00\x00\x49\x45\x4e\x44\xae\x42\x60\x82";
+
+/// Avatar upload, file part "avatar" with file name "avatar.png" and AVATAR_PNG content
+pub fn avatar_upload() -> MultiPartFormDataBuilder {
+    let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+    multipart_form_data_builder.with_static_file("avatar", "avatar.png", "image/png", AVATAR_PNG);
+    multipart_form_data_builder
+}
+
+/// Windows path of windows_path_upload
+pub const WINDOWS_PATH: &str = r"C:\Users\me\file.png";
+
+/// Upload from an old Internet Explorer client, file part "file" with the full Windows path as
+/// file name, e.g. WINDOWS_PATH, and AVATAR_PNG content
+///
+/// File name is written verbatim, backslashes are not escaped, see Part::raw
+pub fn windows_path_upload(windows_path: impl Into<String>) -> MultiPartFormDataBuilder {
+    let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+    multipart_form_data_builder
+        .with_part(Part::bytes("file", windows_path, "image/png", AVATAR_PNG).raw());
+    multipart_form_data_builder
+}
+
+/// Hostile text values, e.g. to run against every form field
+///
+/// Script tags, SQL injection, template and path traversal strings, long and combining Unicode,
+/// right-to-left overrides, zero width characters and an empty value
+pub const FUZZ_TEXTS: &[&str] = &[
+    "<script>alert(1)</script>",
+    "\"><img src=x onerror=alert(1)>",
+    "javascript:alert(1)",
+    "' OR '1'='1",
+    "'; DROP TABLE users; --",
+    "1 UNION SELECT NULL, NULL --",
+    "{{7*7}}${7*7}",
+    "../../../../etc/passwd",
+    "\u{202e}gnp.exe",
+    "\u{200b}\u{200d}\u{feff}",
+    "Z\u{336}\u{334}\u{335}a\u{337}\u{338}l\u{321}\u{322}g\u{327}o\u{328}",
+    "\u{1f600}\u{1f469}\u{200d}\u{1f4bb}",
+    "\u{fdfd}\u{fdfd}\u{fdfd}\u{fdfd}\u{fdfd}\u{fdfd}\u{fdfd}\u{fdfd}",
+    "",
+];
+
+/// Standard EICAR antivirus test file content, harmless but detected by antivirus scanners
+pub const EICAR: &[u8] = br"X5O!P%@AP[4\PZX54(P^)7CC)7}$EICAR-STANDARD-ANTIVIRUS-TEST-FILE!$H+H*";
+
+/// Content types sniffed by with_mismatched_file, (magic bytes, content type)
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+const MAGIC_BYTES: &[(&[u8], &str)] = &[
+    (b"\x89PNG\r\n\x1a\n", "image/png"),
+    (b"\xff\xd8\xff", "image/jpeg"),
+    (b"GIF87a", "image/gif"),
+    (b"GIF89a", "image/gif"),
+    (b"%PDF-", "application/pdf"),
+    (b"PK\x03\x04", "application/zip"),
+    (b"MZ", "application/x-msdownload"),
+    (b"\x7fELF", "application/x-elf"),
+];
+
+/// Common content type mismatches, file parts named "file" whose declared content type
+/// contradicts their magic bytes
+///
+/// Returns (description, part) pairs, e.g. ("executable declared as png", part)
+pub fn content_type_mismatches() -> Vec<(&'static str, Part)> {
+    vec![
+        (
+            "png declared as jpeg",
+            Part::static_file("file", "image.jpg", "image/jpeg", AVATAR_PNG),
+        ),
+        (
+            "png declared as pdf",
+            Part::static_file("file", "document.pdf", "application/pdf", AVATAR_PNG),
+        ),
+        (
+            "executable declared as png",
+            Part::static_file("file", "image.png", "image/png", b"MZ\x90\x00\x03\x00\x00\x00"),
+        ),
+        (
+            "elf declared as text",
+            Part::static_file("file", "notes.txt", "text/plain", b"\x7fELF\x02\x01\x01\x00"),
+        ),
+        (
+            "html declared as gif",
+            Part::static_file("file", "image.gif", "image/gif", b"<html><script>alert(1)</script></html>"),
+        ),
+        (
+            "zip declared as json",
+            Part::static_file("file", "data.json", "application/json", b"PK\x05\x06\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00"),
+        ),
+    ]
+}
+
+/// Content type of content by magic bytes
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+fn sniff_content_type(content: &[u8]) -> Option<&'static str> {
+    MAGIC_BYTES
+        .iter()
+        .find(|(magic, _)| content.starts_with(magic))
+        .map(|(_, content_type)| *content_type)
+}
+
+impl MultiPartFormDataBuilder {
+    /// Add file part declaring a content type that contradicts the file magic bytes, e.g. to test
+    /// content sniffing
+    ///
+    /// The first 8 bytes of the file are read now to check magic bytes, file contents are read
+    /// on build
+    ///
+    /// path is file path
+    ///
+    /// name is form name
+    ///
+    /// declared_type is declared content type
+    ///
+    /// file_name is file name
+    ///
+    /// Panics when declared_type is the content type of the file magic bytes, e.g. "image/png"
+    /// for a PNG file, or when the file cannot be read
+    ///
+    /// Returns &mut MultiPartFormDataBuilder
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    pub fn with_mismatched_file(
+        &mut self,
+        path: impl AsRef<Path>,
+        name: impl Into<String>,
+        declared_type: impl Into<String>,
+        file_name: impl Into<String>,
+    ) -> &mut MultiPartFormDataBuilder {
+        let declared_type = declared_type.into();
+        let mut head = [0; 8];
+        let read = std::fs::File::open(path.as_ref())
+            .and_then(|mut file| std::io::Read::read(&mut file, &mut head))
+            .unwrap_or_else(|error| {
+                panic!("cannot read magic bytes of {:?}: {}", path.as_ref(), error)
+            });
+        assert_ne!(
+            sniff_content_type(&head[..read]),
+            Some(declared_type.as_str()),
+            "declared type {:?} matches magic bytes of {:?}",
+            declared_type,
+            path.as_ref()
+        );
+        self.with_part(Part::file(path, name, declared_type, file_name))
+    }
+
+    /// Add file part with EICAR content, e.g. to test that antivirus scanning quarantines it
+    ///
+    /// name is form name
+    ///
+    /// file_name is file name, e.g. "eicar.com", content type is "application/octet-stream"
+    ///
+    /// Returns &mut MultiPartFormDataBuilder
+    pub fn with_eicar_file(
+        &mut self,
+        name: impl Into<String>,
+        file_name: impl Into<String>,
+    ) -> &mut MultiPartFormDataBuilder {
+        self.with_static_file(name, file_name, DEFAULT_CONTENT_TYPE, EICAR)
+    }
+
+    /// Add one text part named name for every value of FUZZ_TEXTS
+    ///
+    /// Returns &mut MultiPartFormDataBuilder
+    pub fn with_fuzz_texts(&mut self, name: impl Into<String>) -> &mut MultiPartFormDataBuilder {
+        let name = name.into();
+        for value in FUZZ_TEXTS.iter() {
+            self.with_static_text(name.clone(), *value);
+        }
+        self
+    }
+}
+
+/// GIF image that is also valid JavaScript calling alert(1), e.g. to test content validation
+///
+/// Part has file name "image.gif" and content type "image/gif"
+pub fn gif_javascript_polyglot(name: impl Into<String>) -> Part {
+    let mut content = vec![];
+    // width is "/*", opening a JavaScript comment after the GIF89a identifier
+    content.extend_from_slice(b"GIF89a/*\x01\x00\x00\x00\x00");
+    content.extend_from_slice(b",\x00\x00\x00\x00\x01\x00\x01\x00\x00\x02\x02\x44\x01\x00;");
+    content.extend_from_slice(b"*/=1;alert(1);");
+    Part::bytes(name, "image.gif", "image/gif", content)
+}
+
+/// PDF document that is also a ZIP archive with "polyglot.txt", e.g. to test content validation
+///
+/// Part has file name "document.pdf" and content type "application/pdf"
+pub fn pdf_zip_polyglot(name: impl Into<String>) -> Part {
+    let mut content = b"%PDF-1.4\n1 0 obj<</Type/Catalog/Pages 2 0 R>>endobj\n2 0 obj<</Type/Pages/Kids[]/Count 0>>endobj\ntrailer<</Root 1 0 R>>\n%%EOF\n".to_vec();
+    let file_name = b"polyglot.txt";
+    let data = b"polyglot";
+    let mut crc = flate2::Crc::new();
+    crc.update(data);
+    let local_header_offset = content.len() as u32;
+    let mut entry = vec![];
+    entry.extend_from_slice(&0u16.to_le_bytes()); // flags
+    entry.extend_from_slice(&0u16.to_le_bytes()); // stored
+    entry.extend_from_slice(&0u32.to_le_bytes()); // time and date
+    entry.extend_from_slice(&crc.sum().to_le_bytes());
+    entry.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    entry.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    entry.extend_from_slice(&(file_name.len() as u16).to_le_bytes());
+    entry.extend_from_slice(&0u16.to_le_bytes()); // extra field length
+    content.extend_from_slice(b"PK\x03\x04\x14\x00");
+    content.extend_from_slice(&entry);
+    content.extend_from_slice(file_name);
+    content.extend_from_slice(data);
+    let central_directory_offset = content.len() as u32;
+    content.extend_from_slice(b"PK\x01\x02\x14\x00\x14\x00");
+    content.extend_from_slice(&entry);
+    content.extend_from_slice(&[0; 6]); // comment length, disk, internal attributes
+    content.extend_from_slice(&0u32.to_le_bytes()); // external attributes
+    content.extend_from_slice(&local_header_offset.to_le_bytes());
+    content.extend_from_slice(file_name);
+    let central_directory_len = content.len() as u32 - central_directory_offset;
+    content.extend_from_slice(b"PK\x05\x06\x00\x00\x00\x00\x01\x00\x01\x00");
+    content.extend_from_slice(&central_directory_len.to_le_bytes());
+    content.extend_from_slice(&central_directory_offset.to_le_bytes());
+    content.extend_from_slice(&0u16.to_le_bytes()); // comment length
+    Part::bytes(name, "document.pdf", "application/pdf", content)
+}
+
+/// Header smuggled by crlf_injection_cases
+pub const CRLF_INJECTION: &str = "\r\nX-Evil: 1";
+
+/// CRLF injection cases, e.g. to verify the server rejects smuggled part headers
+///
+/// Returns (description, builder) pairs, CRLF_INJECTION is written unescaped into a field name,
+/// a file name and a content type, inside and after the quoted-string
+pub fn crlf_injection_cases() -> Vec<(&'static str, MultiPartFormDataBuilder)> {
+    let cases = [
+        (
+            "field name",
+            Part::raw_text(format!("name{}", CRLF_INJECTION), "value"),
+        ),
+        (
+            "field name after quote",
+            Part::raw_text(format!("name\"{}", CRLF_INJECTION), "value"),
+        ),
+        (
+            "file name",
+            Part::bytes(
+                "file",
+                format!("a.png{}", CRLF_INJECTION),
+                "image/png",
+                AVATAR_PNG,
+            )
+            .raw(),
+        ),
+        (
+            "file name after quote",
+            Part::bytes(
+                "file",
+                format!("a.png\"{}", CRLF_INJECTION),
+                "image/png",
+                AVATAR_PNG,
+            )
+            .raw(),
+        ),
+        (
+            "content type",
+            Part::bytes(
+                "file",
+                "a.png",
+                format!("image/png{}", CRLF_INJECTION),
+                AVATAR_PNG,
+            )
+            .raw(),
+        ),
+    ];
+    cases
+        .into_iter()
+        .map(|(description, part)| {
+            let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+            multipart_form_data_builder.with_part(part);
+            (description, multipart_form_data_builder)
+        })
+        .collect()
+}
+
+/// CSV import, file part "file" with file name "import.csv" and content type "text/csv"
+///
+/// rows are CSV rows, fields with commas, quotes or line breaks are quoted, lines end with CRLF
+pub fn csv_import<R, F>(rows: R) -> MultiPartFormDataBuilder
+where
+    R: IntoIterator,
+    R::Item: IntoIterator<Item = F>,
+    F: AsRef<str>,
+{
+    let mut csv = String::new();
+    for row in rows {
+        let fields: Vec<String> = row
+            .into_iter()
+            .map(|field| csv_field(field.as_ref()))
+            .collect();
+        csv.push_str(&fields.join(","));
+        csv.push_str("\r\n");
+    }
+    let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+    multipart_form_data_builder.with_bytes("file", "import.csv", "text/csv", csv);
+    multipart_form_data_builder
+}
+
+/// Document with JSON metadata, part "metadata" with content type "application/json" followed
+/// by file part "document"
+///
+/// json is metadata content
+///
+/// path is document path, file name is last path component, content type is
+/// "application/octet-stream"
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+pub fn document_with_metadata(
+    json: impl Into<String>,
+    path: impl AsRef<Path>,
+) -> MultiPartFormDataBuilder {
+    let path = path.as_ref();
+    let file_name = path
+        .file_name()
+        .map(|file_name| file_name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+    multipart_form_data_builder
+        .with_part(Part::text("metadata", json).with_content_type("application/json"))
+        .with_file_default_type(path, "document", file_name);
+    multipart_form_data_builder
+}
+
+/// Fields of a browser style S3 POST policy upload form
+#[derive(Clone, Debug, Default)]
+pub struct S3PostPolicy {
+    /// object key, e.g. "uploads/${filename}"
+    pub key: String,
+    /// canned ACL, e.g. "private", no acl field when empty
+    pub acl: String,
+    /// base64 encoded policy document
+    pub policy: String,
+    /// policy signature, sent as "x-amz-signature"
+    pub signature: String,
+    /// other fields, e.g. ("x-amz-credential", "...") or ("success_action_status", "201")
+    pub fields: Vec<(String, String)>,
+    /// metadata, sent as "x-amz-meta-{name}" fields
+    pub metadata: Vec<(String, String)>,
+}
+
+/// S3 POST policy upload, e.g. against MinIO
+///
+/// Fields are key, acl, other fields, metadata, policy and x-amz-signature, file part is renamed
+/// "file" and written last as S3 ignores fields after it, so add no parts to the returned builder
+pub fn s3_post_policy_upload(
+    s3_post_policy: &S3PostPolicy,
+    file: Part,
+) -> MultiPartFormDataBuilder {
+    let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+    multipart_form_data_builder.with_text("key", s3_post_policy.key.clone());
+    if !s3_post_policy.acl.is_empty() {
+        multipart_form_data_builder.with_text("acl", s3_post_policy.acl.clone());
+    }
+    for (name, value) in s3_post_policy.fields.iter() {
+        multipart_form_data_builder.with_text(name.clone(), value.clone());
+    }
+    for (name, value) in s3_post_policy.metadata.iter() {
+        multipart_form_data_builder.with_text(format!("x-amz-meta-{}", name), value.clone());
+    }
+    multipart_form_data_builder
+        .with_text("policy", s3_post_policy.policy.clone())
+        .with_text("x-amz-signature", s3_post_policy.signature.clone())
+        .with_part(Part {
+            name: b"file".to_vec(),
+            ..file
+        });
+    multipart_form_data_builder
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MultiPartFormDataParser;
+
+    #[test]
+    fn test_should_build_csv_import_with_quoted_fields() {
+        let ((_, content_type), body) =
+            csv_import([["id", "name"], ["1", "Doe, \"Jane\""]]).build();
+
+        let parsed = MultiPartFormDataParser::parse(&content_type, &body).unwrap();
+        parsed
+            .assert_part("file")
+            .assert_content_type("text/csv")
+            .assert_text("id,name\r\n1,\"Doe, \"\"Jane\"\"\"\r\n");
+    }
+
+    #[test]
+    fn test_should_build_s3_post_policy_upload_with_file_last() {
+        let s3_post_policy = S3PostPolicy {
+            key: "uploads/${filename}".to_string(),
+            acl: "private".to_string(),
+            policy: "eyJ9".to_string(),
+            signature: "abc".to_string(),
+            fields: vec![(
+                "x-amz-algorithm".to_string(),
+                "AWS4-HMAC-SHA256".to_string(),
+            )],
+            metadata: vec![("owner".to_string(), "1".to_string())],
+        };
+        let ((_, content_type), body) = s3_post_policy_upload(
+            &s3_post_policy,
+            Part::bytes("any", "a.txt", "text/plain", "a"),
+        )
+        .build();
+
+        let parsed = MultiPartFormDataParser::parse(&content_type, &body).unwrap();
+        let names: Vec<_> = parsed
+            .parts
+            .iter()
+            .map(|part| part.name.as_deref().unwrap())
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "key",
+                "acl",
+                "x-amz-algorithm",
+                "x-amz-meta-owner",
+                "policy",
+                "x-amz-signature",
+                "file"
+            ]
+        );
+        parsed.assert_part("file").assert_file_name("a.txt");
+    }
+
+    #[test]
+    fn test_should_build_document_with_metadata() {
+        let ((_, content_type), body) =
+            document_with_metadata(r#"{"title":"sample"}"#, "tests/sample.png").build();
+
+        let parsed = MultiPartFormDataParser::parse(&content_type, &body).unwrap();
+        parsed.assert_part_count(2);
+        parsed
+            .assert_part("metadata")
+            .assert_content_type("application/json")
+            .assert_text(r#"{"title":"sample"}"#);
+        parsed
+            .assert_part("document")
+            .assert_file_name("sample.png")
+            .assert_data(std::fs::read("tests/sample.png").unwrap());
+    }
+
+    #[test]
+    fn test_should_build_windows_path_upload_verbatim() {
+        let (_, body) = windows_path_upload(WINDOWS_PATH).build();
+
+        let body = String::from_utf8_lossy(&body);
+        assert!(body.contains(r#"name="file"; filename="C:\Users\me\file.png""#));
+    }
+
+    #[test]
+    fn test_should_inject_crlf_in_every_case() {
+        let cases = crlf_injection_cases();
+
+        assert_eq!(cases.len(), 5);
+        for (description, multipart_form_data_builder) in cases {
+            let (_, body) = multipart_form_data_builder.build();
+            assert!(
+                String::from_utf8_lossy(&body).contains(CRLF_INJECTION),
+                "{} not injected",
+                description
+            );
+        }
+    }
+
+    #[test]
+    fn test_should_add_fuzz_texts() {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder.with_fuzz_texts("comment");
+        let ((_, content_type), body) = multipart_form_data_builder.build();
+
+        let parsed = MultiPartFormDataParser::parse(&content_type, &body).unwrap();
+        let values: Vec<_> = parsed
+            .parts
+            .iter()
+            .map(|part| String::from_utf8(part.data.clone()).unwrap())
+            .collect();
+        assert_eq!(values, FUZZ_TEXTS);
+    }
+
+    #[test]
+    fn test_should_add_eicar_file() {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder.with_eicar_file("file", "eicar.com");
+        let ((_, content_type), body) = multipart_form_data_builder.build();
+
+        let parsed = MultiPartFormDataParser::parse(&content_type, &body).unwrap();
+        parsed
+            .assert_part("file")
+            .assert_file_name("eicar.com")
+            .assert_data(EICAR);
+        assert_eq!(EICAR.len(), 68);
+    }
+
+    #[test]
+    fn test_should_build_gif_javascript_polyglot() {
+        let content = gif_javascript_polyglot("file").read();
+
+        assert!(content.starts_with(b"GIF89a/*"));
+        let comment_end = content
+            .windows(2)
+            .position(|window| window == b"*/")
+            .unwrap();
+        assert_eq!(content[comment_end - 1], b';');
+        assert!(content.ends_with(b"*/=1;alert(1);"));
+    }
+
+    #[test]
+    fn test_should_build_pdf_zip_polyglot_with_valid_offsets() {
+        let content = pdf_zip_polyglot("file").read();
+
+        assert!(content.starts_with(b"%PDF-1.4\n"));
+        let end_of_central_directory = &content[content.len() - 22..];
+        assert!(end_of_central_directory.starts_with(b"PK\x05\x06"));
+        let offset = u32::from_le_bytes(end_of_central_directory[16..20].try_into().unwrap());
+        let central_directory = &content[offset as usize..];
+        assert!(central_directory.starts_with(b"PK\x01\x02"));
+        let local_header_offset =
+            u32::from_le_bytes(central_directory[42..46].try_into().unwrap()) as usize;
+        assert!(content[local_header_offset..].starts_with(b"PK\x03\x04"));
+        assert!(content[local_header_offset + 30..].starts_with(b"polyglot.txtpolyglot"));
+    }
+
+    #[test]
+    fn test_should_contradict_magic_bytes_in_every_mismatch() {
+        for (description, part) in content_type_mismatches() {
+            assert_ne!(
+                sniff_content_type(&part.read()),
+                part.content_type.as_deref(),
+                "{}",
+                description
+            );
+        }
+    }
+
+    #[test]
+    fn test_should_add_mismatched_file() {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder.with_mismatched_file(
+            "tests/sample.png",
+            "sample",
+            "image/jpeg",
+            "sample.jpg",
+        );
+        let ((_, content_type), body) = multipart_form_data_builder.build();
+
+        let parsed = MultiPartFormDataParser::parse(&content_type, &body).unwrap();
+        parsed
+            .assert_part("sample")
+            .assert_content_type("image/jpeg");
+    }
+
+    #[test]
+    #[should_panic(expected = "matches magic bytes")]
+    fn test_should_panic_when_declared_type_matches_magic_bytes() {
+        MultiPartFormDataBuilder::new().with_mismatched_file(
+            "tests/sample.png",
+            "sample",
+            "image/png",
+            "sample.png",
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot read magic bytes of \"tests/missing.png\"")]
+    fn test_should_panic_with_path_when_mismatched_file_is_missing() {
+        MultiPartFormDataBuilder::new().with_mismatched_file(
+            "tests/missing.png",
+            "sample",
+            "image/jpeg",
+            "missing.png",
+        );
+    }
+}
@@ -0,0 +1,193 @@
+use crate::{
+    boundary::{boundary_parameter, checked_or_random_boundary},
+    mime::write_multipart,
+    MimePart,
+};
+
+/// Simple builder for multipart/related test, e.g. MTOM/XOP SOAP attachments
+///
+/// Root part is written first and referenced by start parameter, other parts are referenced
+/// from root content by "cid:content_id"
+///
+/// # Examples
+///
+/// ```
+/// use actix_multipart_test::{MimePart, MultipartRelatedBuilder};
+///
+/// let mut multipart_related_builder = MultipartRelatedBuilder::new();
+/// multipart_related_builder
+///     .with_root(
+///         "root@example.com",
+///         MimePart::new(
+///             "application/xop+xml; charset=UTF-8; type=\"text/xml\"",
+///             r#"<Envelope><xop:Include href="cid:image@example.com"/></Envelope>"#,
+///         ),
+///     )
+///     .with_start_info("text/xml")
+///     .with_part("image@example.com", MimePart::file("tests/sample.png", "image/png"));
+/// let (header, body) = multipart_related_builder.build();
+/// ```
+#[derive(Default)]
+pub struct MultipartRelatedBuilder {
+    root: Option<MimePart>,
+    start: Option<String>,
+    start_info: Option<String>,
+    parts: Vec<MimePart>,
+    boundary: Option<String>,
+}
+
+impl MultipartRelatedBuilder {
+    /// Create new MultipartRelatedBuilder
+    pub fn new() -> MultipartRelatedBuilder {
+        MultipartRelatedBuilder {
+            root: None,
+            start: None,
+            start_info: None,
+            parts: vec![],
+            boundary: None,
+        }
+    }
+
+    /// Set a fixed boundary used by every build, e.g. for snapshot or golden tests
+    ///
+    /// boundary is checked against RFC 2046 on build, builds panic when it is invalid
+    ///
+    /// Returns &mut MultipartRelatedBuilder
+    pub fn with_boundary(&mut self, boundary: impl Into<String>) -> &mut MultipartRelatedBuilder {
+        self.boundary = Some(boundary.into());
+        self
+    }
+
+    /// Set root part, replaces previous root
+    ///
+    /// content_id is Content-ID without angle brackets, used as start parameter
+    ///
+    /// Returns &mut MultipartRelatedBuilder
+    pub fn with_root(
+        &mut self,
+        content_id: impl Into<String>,
+        part: MimePart,
+    ) -> &mut MultipartRelatedBuilder {
+        let content_id = content_id.into();
+        self.root = Some(with_content_id(part, &content_id));
+        self.start = Some(content_id);
+        self
+    }
+
+    /// Set start-info parameter, e.g. "text/xml" for SOAP 1.1 XOP
+    ///
+    /// Returns &mut MultipartRelatedBuilder
+    pub fn with_start_info(
+        &mut self,
+        start_info: impl Into<String>,
+    ) -> &mut MultipartRelatedBuilder {
+        self.start_info = Some(start_info.into());
+        self
+    }
+
+    /// Add related part
+    ///
+    /// content_id is Content-ID without angle brackets, referenced as "cid:content_id"
+    ///
+    /// Returns &mut MultipartRelatedBuilder
+    pub fn with_part(
+        &mut self,
+        content_id: impl Into<String>,
+        part: MimePart,
+    ) -> &mut MultipartRelatedBuilder {
+        self.parts.push(with_content_id(part, &content_id.into()));
+        self
+    }
+
+    /// Build multipart/related
+    ///
+    /// Returns ((header_name, header_value), body)
+    ///
+    /// header_name is "Content-Type"
+    ///
+    /// header_value is "multipart/related; type=...; start=...; boundary=...", type is
+    /// media type of root part, or of first part without root
+    ///
+    /// body is binary data
+    pub fn build(&self) -> ((String, String), Vec<u8>) {
+        let boundary = checked_or_random_boundary(self.boundary.as_deref());
+        let parts: Vec<MimePart> = self.root.iter().chain(self.parts.iter()).cloned().collect();
+        let mut header_value = "multipart/related".to_string();
+        if let Some(part) = parts.first() {
+            let media_type = part.content_type().split(';').next().unwrap_or("").trim();
+            header_value.push_str(&format!("; type=\"{}\"", media_type));
+        }
+        if let Some(start) = &self.start {
+            header_value.push_str(&format!("; start=\"<{}>\"", start));
+        }
+        if let Some(start_info) = &self.start_info {
+            header_value.push_str(&format!("; start-info=\"{}\"", start_info));
+        }
+        header_value.push_str(&format!("; boundary={}", boundary_parameter(&boundary)));
+        (
+            ("Content-Type".to_string(), header_value),
+            write_multipart(&boundary, &parts),
+        )
+    }
+}
+
+fn with_content_id(part: MimePart, content_id: &str) -> MimePart {
+    part.with_header("Content-ID", format!("<{}>", content_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MultiPartFormDataParser;
+
+    #[test]
+    fn test_should_build_multipart_related_with_root_first() {
+        let mut multipart_related_builder = MultipartRelatedBuilder::new();
+        multipart_related_builder
+            .with_part(
+                "image@example.com",
+                MimePart::file("tests/sample.png", "image/png"),
+            )
+            .with_root(
+                "root@example.com",
+                MimePart::new(
+                    "application/xop+xml; charset=UTF-8",
+                    r#"<xop:Include href="cid:image@example.com"/>"#,
+                ),
+            )
+            .with_start_info("text/xml");
+        let ((_, content_type), body) = multipart_related_builder.build();
+
+        assert!(content_type.starts_with(
+            "multipart/related; type=\"application/xop+xml\"; start=\"<root@example.com>\"; start-info=\"text/xml\"; boundary="
+        ));
+        let parsed = MultiPartFormDataParser::parse(&content_type, &body).unwrap();
+        assert_eq!(parsed.parts.len(), 2);
+        parsed.parts[0].assert_header("Content-ID", "<root@example.com>");
+        parsed.parts[1]
+            .assert_header("Content-ID", "<image@example.com>")
+            .assert_content_type("image/png");
+    }
+
+    #[test]
+    fn test_should_build_multipart_related_with_fixed_boundary() {
+        let mut multipart_related_builder = MultipartRelatedBuilder::new();
+        multipart_related_builder
+            .with_root("root@example.com", MimePart::new("text/xml", "<Envelope/>"))
+            .with_boundary("related");
+        let ((_, content_type), body) = multipart_related_builder.build();
+
+        assert_eq!(
+            content_type,
+            "multipart/related; type=\"text/xml\"; start=\"<root@example.com>\"; boundary=related"
+        );
+        assert_eq!(body, multipart_related_builder.build().1);
+        assert!(body.starts_with(b"--related\r\n"));
+    }
+
+    #[test]
+    #[should_panic(expected = "boundary is empty")]
+    fn test_should_panic_on_invalid_related_boundary() {
+        MultipartRelatedBuilder::new().with_boundary("").build();
+    }
+}
@@ -0,0 +1,225 @@
+use std::{
+    collections::HashMap,
+    io::{Read, Write},
+    net::{TcpStream, ToSocketAddrs},
+    sync::{Mutex, OnceLock},
+    time::Duration,
+};
+
+use bytes::Bytes;
+
+use crate::{part::Part, MultiPartFormDataBuilder};
+
+/// Connect, read and write timeout of downloads
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Maximum number of redirects followed by a download
+const MAX_REDIRECTS: usize = 5;
+
+/// Downloaded contents by url, shared by all builders of the process
+static DOWNLOADS: OnceLock<Mutex<HashMap<String, Bytes>>> = OnceLock::new();
+
+impl MultiPartFormDataBuilder {
+    /// Add file downloaded from url to multipart/form-data, e.g. a large fixture of an artifact store
+    ///
+    /// name is form name
+    ///
+    /// file_name is file name
+    ///
+    /// content_type is file content type
+    ///
+    /// url is a plain http:// url, downloaded on first build with a 30 seconds timeout, following
+    /// up to 5 redirects, and cached for the whole process until clear_download_cache
+    ///
+    /// https:// urls are not supported as no TLS client is included, use with_lazy with an HTTPS
+    /// client for them
+    ///
+    /// Panics at build when the download fails or does not answer 200
+    pub fn with_url(
+        &mut self,
+        name: impl Into<String>,
+        file_name: impl Into<String>,
+        content_type: impl Into<String>,
+        url: impl Into<String>,
+    ) -> &mut MultiPartFormDataBuilder {
+        let url = url.into();
+        self.with_part(Part::lazy(name, file_name, content_type, move || {
+            download(&url)
+        }))
+    }
+}
+
+/// Drop downloaded contents cached by with_url, urls are downloaded again on next build
+///
+/// Parts already built keep sharing their content
+pub fn clear_download_cache() {
+    if let Some(downloads) = DOWNLOADS.get() {
+        downloads.lock().unwrap().clear();
+    }
+}
+
+/// Content of url, downloaded once per process
+pub(crate) fn download(url: &str) -> Bytes {
+    let downloads = DOWNLOADS.get_or_init(Default::default);
+    if let Some(content) = downloads.lock().unwrap().get(url) {
+        return content.clone();
+    }
+    let mut location = url.to_string();
+    for _ in 0..=MAX_REDIRECTS {
+        match get(&location) {
+            Response::Content(content) => {
+                downloads
+                    .lock()
+                    .unwrap()
+                    .insert(url.to_string(), content.clone());
+                return content;
+            }
+            Response::Redirect(redirect) => location = redirect,
+        }
+    }
+    panic!("failed to download `{}`: too many redirects", url)
+}
+
+enum Response {
+    Content(Bytes),
+    Redirect(String),
+}
+
+/// HTTP/1.0 GET of url, so the response is never chunked
+fn get(url: &str) -> Response {
+    let rest = url.strip_prefix("http://").unwrap_or_else(|| {
+        panic!(
+            "failed to download `{}`: only http:// urls are supported, use with_lazy with an HTTPS client",
+            url
+        )
+    });
+    let (host, path) = match rest.find('/') {
+        Some(index) => rest.split_at(index),
+        None => (rest, "/"),
+    };
+    let address = match host.contains(':') {
+        true => host.to_string(),
+        false => format!("{}:80", host),
+    };
+    let mut response = vec![];
+    address
+        .to_socket_addrs()
+        .and_then(|mut addresses| {
+            let address = addresses.next().ok_or(std::io::ErrorKind::NotFound)?;
+            let mut stream = TcpStream::connect_timeout(&address, TIMEOUT)?;
+            stream.set_read_timeout(Some(TIMEOUT))?;
+            stream.set_write_timeout(Some(TIMEOUT))?;
+            // one write, servers may answer and close after the first read
+            stream
+                .write_all(format!("GET {} HTTP/1.0\r\nHost: {}\r\n\r\n", path, host).as_bytes())?;
+            stream.read_to_end(&mut response)
+        })
+        .unwrap_or_else(|error| panic!("failed to download `{}`: {}", url, error));
+    let head_len = response
+        .windows(4)
+        .position(|window| window == b"\r\n\r\n")
+        .unwrap_or_else(|| panic!("failed to download `{}`: incomplete response", url));
+    let head = String::from_utf8_lossy(&response[..head_len]).into_owned();
+    let mut lines = head.lines();
+    let status_line = lines.next().unwrap_or_default();
+    match status_line.split(' ').nth(1) {
+        Some("200") => Response::Content(Bytes::from(response).slice(head_len + 4..)),
+        Some("301" | "302" | "303" | "307" | "308") => {
+            let location = lines
+                .filter_map(|line| line.split_once(':'))
+                .find(|(name, _)| name.trim().eq_ignore_ascii_case("Location"))
+                .map(|(_, value)| value.trim())
+                .unwrap_or_else(|| panic!("failed to download `{}`: {}", url, status_line));
+            Response::Redirect(match location.starts_with('/') {
+                true => format!("http://{}{}", host, location),
+                false => location.to_string(),
+            })
+        }
+        _ => panic!("failed to download `{}`: {}", url, status_line),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::TcpListener;
+
+    use super::*;
+    use crate::MultiPartFormDataParser;
+
+    /// Serve response to one connection, returns the url of path
+    fn serve_once(path: &str, response: impl Into<Vec<u8>>) -> String {
+        let response = response.into();
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}{}", listener.local_addr().unwrap(), path);
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = vec![];
+            let mut buf = [0; 1024];
+            while !request.ends_with(b"\r\n\r\n") {
+                match stream.read(&mut buf).unwrap() {
+                    0 => break,
+                    read => request.extend_from_slice(&buf[..read]),
+                }
+            }
+            stream.write_all(&response).unwrap();
+        });
+        url
+    }
+
+    #[test]
+    fn test_should_download_url_once_at_build() {
+        let url = serve_once(
+            "/fixtures/sample.bin",
+            b"HTTP/1.0 200 OK\r\nContent-Length: 7\r\n\r\ncontent".as_slice(),
+        );
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder.with_url(
+            "sample",
+            "sample.bin",
+            "application/octet-stream",
+            &url,
+        );
+
+        for _ in 0..2 {
+            let ((_, content_type), body) = multipart_form_data_builder.build();
+            let parsed = MultiPartFormDataParser::parse(&content_type, &body).unwrap();
+            parsed.assert_part("sample").assert_data(b"content");
+        }
+        assert_eq!(download(&url).as_ref(), b"content");
+    }
+
+    #[test]
+    fn test_should_follow_redirects_and_share_downloads() {
+        let target = serve_once(
+            "/fixtures/moved.bin",
+            b"HTTP/1.0 200 OK\r\nContent-Length: 5\r\n\r\nmoved".as_slice(),
+        );
+        let url = serve_once(
+            "/fixtures/old.bin",
+            format!("HTTP/1.0 302 Found\r\nLocation: {}\r\n\r\n", target),
+        );
+
+        assert_eq!(download(&url).as_ref(), b"moved");
+        assert_eq!(download(&url).as_ptr(), download(&url).as_ptr());
+    }
+
+    #[test]
+    #[should_panic(expected = "only http:// urls are supported")]
+    fn test_should_panic_on_https_urls() {
+        download("https://example.com/fixture.bin");
+    }
+
+    #[test]
+    #[should_panic(expected = "404 Not Found")]
+    fn test_should_panic_when_download_fails() {
+        let url = serve_once("/missing.bin", b"HTTP/1.0 404 Not Found\r\n\r\n".as_slice());
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder.with_url(
+            "sample",
+            "sample.bin",
+            "application/octet-stream",
+            url,
+        );
+        multipart_form_data_builder.build();
+    }
+}
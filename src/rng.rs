@@ -0,0 +1,46 @@
+/// Deterministic random number generator, splitmix64, same seed gives same sequence everywhere
+pub(crate) struct Rng(u64);
+
+impl Rng {
+    pub(crate) fn new(seed: u64) -> Rng {
+        Rng(seed)
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Random number below bound, bound must not be 0
+    pub(crate) fn below(&mut self, bound: usize) -> usize {
+        (self.next_u64() % bound as u64) as usize
+    }
+
+    /// Fisher-Yates shuffle
+    pub(crate) fn shuffle<T>(&mut self, items: &mut [T]) {
+        for index in (1..items.len()).rev() {
+            items.swap(index, self.below(index + 1));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_should_shuffle_reproducibly() {
+        let mut items: Vec<usize> = (0..10).collect();
+        let mut other = items.clone();
+        Rng::new(7).shuffle(&mut items);
+        Rng::new(7).shuffle(&mut other);
+
+        assert_eq!(items, other);
+        assert_ne!(items, (0..10).collect::<Vec<_>>());
+        other.sort();
+        assert_eq!(other, (0..10).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,260 @@
+use std::fmt;
+
+use crate::{
+    MultiPartFormDataBuilder, MultiPartFormDataParser, ParsedMultiPartFormData, PayloadDigest,
+};
+
+/// Token replacing the random boundary in snapshot strings
+pub const SNAPSHOT_BOUNDARY: &str = "BOUNDARY";
+
+impl MultiPartFormDataBuilder {
+    /// Render body as a stable string for snapshot testing
+    ///
+    /// boundary is replaced by "BOUNDARY", lines end with "\n"
+    ///
+    /// binary contents are replaced by "<binary N bytes sha256=...>", sensitive contents by
+    /// "<redacted N bytes>"
+    ///
+    /// Bodies the parser rejects, e.g. with line breaks in headers added with Part::with_header,
+    /// are rendered like Display with "\n" line endings
+    pub fn to_snapshot_string(&self) -> String {
+        let ((_, content_type), body) = self.build();
+        let redacted: Vec<bool> = self.parts.iter().map(|part| part.sensitive).collect();
+        match MultiPartFormDataParser::parse(&content_type, &body) {
+            Ok(parsed) => parsed.render_snapshot(&redacted),
+            Err(_) => self.dump(SNAPSHOT_BOUNDARY).replace("\r\n", "\n"),
+        }
+    }
+
+    /// Boundary of renders, fixed boundary when set, SNAPSHOT_BOUNDARY otherwise
+    pub(crate) fn render_boundary(&self) -> &str {
+        self.boundary.as_deref().unwrap_or(SNAPSHOT_BOUNDARY)
+    }
+
+    /// Payload as text with boundary, binary and sensitive contents are replaced by markers
+    fn dump(&self, boundary: &str) -> String {
+        let mut dump = String::new();
+        for part in self.parts(boundary).iter() {
+            let mut head = vec![];
+            part.write_head(boundary, &mut head);
+            dump.push_str(&String::from_utf8_lossy(&head));
+            let mut content = vec![];
+            part.read_into(&mut content, self.file_cache.as_ref());
+            match std::str::from_utf8(&content) {
+                _ if part.sensitive => {
+                    dump.push_str(&format!("<redacted {} bytes>", content.len()))
+                }
+                Ok(text) if !is_binary(text) => dump.push_str(text),
+                _ => dump.push_str(&format!("<{} bytes>", content.len())),
+            }
+            dump.push_str("\r\n");
+        }
+        dump.push_str(&format!("--{}--\r\n", boundary));
+        dump
+    }
+}
+
+impl ParsedMultiPartFormData {
+    /// Render parts as a stable string for snapshot testing
+    ///
+    /// boundary is replaced by "BOUNDARY", lines end with "\n"
+    ///
+    /// binary contents are replaced by "<binary N bytes sha256=...>"
+    pub fn to_snapshot_string(&self) -> String {
+        self.render_snapshot(&[])
+    }
+
+    /// Render snapshot, contents of parts at true indexes of redacted are masked
+    fn render_snapshot(&self, redacted: &[bool]) -> String {
+        let mut snapshot = String::new();
+        for (index, part) in self.parts.iter().enumerate() {
+            snapshot.push_str(&format!("--{}\n", SNAPSHOT_BOUNDARY));
+            for (name, value) in part.headers.iter() {
+                snapshot.push_str(&format!("{}: {}\n", name, value));
+            }
+            snapshot.push('\n');
+            if redacted.get(index).copied().unwrap_or(false) {
+                snapshot.push_str(&format!("<redacted {} bytes>\n", part.data.len()));
+                continue;
+            }
+            match part.text().filter(|text| !is_binary(text)) {
+                Some(text) => snapshot.push_str(text),
+                None => snapshot.push_str(&format!(
+                    "<binary {} bytes sha256={}>",
+                    part.data.len(),
+                    PayloadDigest::of(&part.data).sha256
+                )),
+            }
+            snapshot.push('\n');
+        }
+        snapshot.push_str(&format!("--{}--\n", SNAPSHOT_BOUNDARY));
+        snapshot
+    }
+}
+
+/// Complete payload as text, binary contents are replaced by "<N bytes>", sensitive contents by
+/// "<redacted N bytes>"
+///
+/// Boundary is the fixed boundary when set, "BOUNDARY" otherwise, so rendering never uses up a
+/// generated boundary, line endings are CRLF as built
+impl fmt::Display for MultiPartFormDataBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.dump(self.render_boundary()))
+    }
+}
+
+fn is_binary(text: &str) -> bool {
+    text.chars()
+        .any(|char| char.is_control() && !matches!(char, '\r' | '\n' | '\t'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Part;
+
+    #[test]
+    fn test_should_redact_sensitive_parts() {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder
+            .with_part(Part::text("token", "secret").sensitive())
+            .with_text("name", "some_name");
+
+        let snapshot = multipart_form_data_builder.to_snapshot_string();
+
+        assert!(!snapshot.contains("secret"));
+        assert!(snapshot.contains("Content-Length: 6\n\n<redacted 6 bytes>\n--BOUNDARY\n"));
+        assert!(snapshot.contains("\nsome_name\n"));
+    }
+
+    #[test]
+    fn test_should_render_stable_snapshot() {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder.with_file(
+            "tests/sample.png",
+            "sample",
+            "image/png",
+            "sample.png",
+        );
+        multipart_form_data_builder.with_text("name", "some_name");
+
+        let snapshot = multipart_form_data_builder.to_snapshot_string();
+        let sample = std::fs::read("tests/sample.png").unwrap();
+
+        assert_eq!(snapshot, multipart_form_data_builder.to_snapshot_string());
+        assert_eq!(
+            snapshot,
+            format!(
+                "--BOUNDARY\n\
+                 Content-Disposition: form-data; name=\"sample\"; filename=\"sample.png\"\n\
+                 Content-Type: image/png\n\
+                 Content-Length: {}\n\
+                 \n\
+                 <binary {} bytes sha256={}>\n\
+                 --BOUNDARY\n\
+                 Content-Disposition: form-data; name=\"name\"\n\
+                 Content-Type: text/plain\n\
+                 Content-Length: 9\n\
+                 \n\
+                 some_name\n\
+                 --BOUNDARY--\n",
+                sample.len(),
+                sample.len(),
+                PayloadDigest::of(&sample).sha256
+            )
+        );
+    }
+
+    #[test]
+    fn test_should_display_payload_with_binary_markers() {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder
+            .with_file("tests/sample.png", "sample", "image/png", "sample.png")
+            .with_text("name", "some_name")
+            .with_boundary("boundary");
+
+        let text = multipart_form_data_builder.to_string();
+        let sample_len = std::fs::metadata("tests/sample.png").unwrap().len();
+
+        assert_eq!(
+            text,
+            format!(
+                "--boundary\r\n\
+                 Content-Disposition: form-data; name=\"sample\"; filename=\"sample.png\"\r\n\
+                 Content-Type: image/png\r\n\
+                 Content-Length: {0}\r\n\
+                 \r\n\
+                 <{0} bytes>\r\n\
+                 --boundary\r\n\
+                 Content-Disposition: form-data; name=\"name\"\r\n\
+                 Content-Type: text/plain\r\n\
+                 Content-Length: 9\r\n\
+                 \r\n\
+                 some_name\r\n\
+                 --boundary--\r\n",
+                sample_len
+            )
+        );
+    }
+
+    #[test]
+    fn test_should_fall_back_to_dump_when_snapshot_is_not_parsable() {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder
+            .with_part(Part::text("name", "some_name").with_header("X-Broken", "a\r\nbroken"))
+            .with_part(Part::text("token", "secret").sensitive());
+        let ((_, content_type), body) = multipart_form_data_builder.build();
+        assert!(MultiPartFormDataParser::parse(&content_type, &body).is_err());
+
+        let snapshot = multipart_form_data_builder.to_snapshot_string();
+
+        assert!(snapshot.starts_with("--BOUNDARY\nContent-Disposition: form-data; name=\"name\"\n"));
+        assert!(snapshot.contains("X-Broken: a\nbroken\n"));
+        assert!(snapshot.contains("\n<redacted 6 bytes>\n--BOUNDARY--\n"));
+        assert!(!snapshot.contains("secret"));
+    }
+
+    #[test]
+    fn test_should_redact_sensitive_parts_in_display() {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder.with_part(Part::text("token", "secret").sensitive());
+
+        let text = multipart_form_data_builder.to_string();
+
+        assert!(!text.contains("secret"));
+        assert!(text.contains("\r\n<redacted 6 bytes>\r\n"));
+    }
+
+    #[test]
+    fn test_should_display_without_using_up_generated_boundaries() {
+        let counter = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let generated = counter.clone();
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder
+            .with_text("name", "some_name")
+            .with_boundary_generator(move || {
+                let count = generated.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                format!("boundary-{}", count)
+            });
+
+        assert!(multipart_form_data_builder
+            .to_string()
+            .starts_with("--BOUNDARY\r\n"));
+        assert!(multipart_form_data_builder
+            .to_curl_command("http://localhost/upload")
+            .contains("'name=some_name'"));
+        assert_eq!(multipart_form_data_builder.boundary(), None);
+        let (_, body) = multipart_form_data_builder.build();
+        assert!(body.starts_with(b"--boundary-0\r\n"));
+    }
+
+    #[test]
+    fn test_should_display_golden_mode_without_boundary() {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder
+            .with_golden_mode("golden")
+            .with_boundary_generator(|| "generated".to_string());
+
+        assert_eq!(multipart_form_data_builder.to_string(), "--BOUNDARY--\r\n");
+    }
+}
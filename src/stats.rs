@@ -0,0 +1,108 @@
+use crate::{boundary::RANDOM_BOUNDARY_LEN, MultiPartFormDataBuilder};
+
+/// Sizes of a multipart/form-data body as built
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FormStats {
+    /// number of parts
+    pub part_count: usize,
+    /// sizes of each part in written order
+    pub parts: Vec<PartStats>,
+    /// bytes that are not part content, i.e. delimiters, part headers and line breaks
+    pub header_overhead: usize,
+    /// body size in bytes, before body encoding
+    pub total_size: usize,
+}
+
+/// Sizes of an encoded part
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PartStats {
+    /// form name, lossy UTF-8
+    pub name: String,
+    /// content size in bytes
+    pub content_size: usize,
+    /// size of delimiter, headers, content and trailing CRLF in bytes
+    pub encoded_size: usize,
+}
+
+impl MultiPartFormDataBuilder {
+    /// Sizes of the body as built, file parts are measured with metadata
+    ///
+    /// Sizes use the fixed boundary when set, otherwise a placeholder as long as the boundary of
+    /// the last build, or as a random boundary before the first build, so they match the
+    /// following build when boundaries have the same length, e.g. uuid or fixed boundaries
+    ///
+    /// No boundary is generated, so stats never changes later builds or boundary
+    pub fn stats(&self) -> FormStats {
+        let boundary = match self.boundary() {
+            Some(boundary) => boundary,
+            None => "-".repeat(RANDOM_BOUNDARY_LEN),
+        };
+        let parts: Vec<PartStats> = self
+            .parts(&boundary)
+            .iter()
+            .map(|part| PartStats {
+                name: String::from_utf8_lossy(&part.name).into_owned(),
+                content_size: part.content_len(),
+                encoded_size: part.encoded_len(&boundary),
+            })
+            .collect();
+        let total_size =
+            parts.iter().map(|part| part.encoded_size).sum::<usize>() + boundary.len() + 6;
+        FormStats {
+            part_count: parts.len(),
+            header_overhead: total_size - parts.iter().map(|part| part.content_size).sum::<usize>(),
+            parts,
+            total_size,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_should_report_sizes_of_built_body() {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder
+            .with_file("tests/sample.png", "sample", "image/png", "sample.png")
+            .with_text("name", "some_name")
+            .with_boundary("boundary");
+
+        let stats = multipart_form_data_builder.stats();
+        let (_, body) = multipart_form_data_builder.build();
+        let sample_len = std::fs::metadata("tests/sample.png").unwrap().len() as usize;
+
+        assert_eq!(stats.part_count, 2);
+        assert_eq!(stats.total_size, body.len());
+        assert_eq!(stats.parts[0].name, "sample");
+        assert_eq!(stats.parts[0].content_size, sample_len);
+        assert_eq!(stats.parts[1].content_size, 9);
+        assert_eq!(stats.header_overhead, body.len() - sample_len - 9);
+        assert_eq!(
+            stats
+                .parts
+                .iter()
+                .map(|part| part.encoded_size)
+                .sum::<usize>(),
+            body.len() - "--boundary--\r\n".len()
+        );
+    }
+
+    #[test]
+    fn test_should_not_generate_boundary_for_stats() {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder.with_text("name", "some_name");
+
+        let stats = multipart_form_data_builder.stats();
+        assert_eq!(multipart_form_data_builder.boundary(), None);
+        let (_, body) = multipart_form_data_builder.build();
+        assert_eq!(stats.total_size, body.len());
+
+        multipart_form_data_builder.with_boundary_generator(|| "generated".to_string());
+        multipart_form_data_builder.build();
+        let stats = multipart_form_data_builder.stats();
+        let (_, body) = multipart_form_data_builder.build();
+        assert_eq!(stats.total_size, body.len());
+    }
+}
@@ -0,0 +1,271 @@
+use std::{
+    collections::VecDeque,
+    fs::File,
+    io::{self, Read, Write},
+    sync::Arc,
+};
+
+use bytes::{Buf, Bytes};
+
+use crate::{
+    cancel::CancellationToken,
+    content_type_header,
+    part::{Part, PartSource},
+    MultiPartFormDataBuilder,
+};
+
+/// Reader over a multipart/form-data body
+///
+/// File contents are read in chunks while reading, only one file is open at a time
+pub struct MultiPartFormDataReader {
+    segments: VecDeque<Segment>,
+    len: u64,
+    read: u64,
+    progress: Option<Progress>,
+    cancellation: Option<CancellationToken>,
+}
+
+/// Progress callback, called with (written, total) bytes
+pub(crate) type Progress = Arc<dyn Fn(u64, u64) + Send + Sync>;
+
+enum Segment {
+    Bytes(Bytes),
+    Path(std::path::PathBuf),
+    File(File),
+}
+
+impl MultiPartFormDataReader {
+    fn new(boundary: &str, parts: &[Part]) -> MultiPartFormDataReader {
+        #[cfg(feature = "tracing")]
+        let _span = crate::trace::build_span(boundary, parts);
+        let crlf = Bytes::from_static(b"\r\n");
+        let mut segments = VecDeque::with_capacity(parts.len() * 3 + 1);
+        for part in parts.iter() {
+            let mut head = vec![];
+            part.write_head(boundary, &mut head);
+            segments.push_back(Segment::Bytes(head.into()));
+            segments.push_back(match &part.source {
+                PartSource::File(path) => Segment::Path(path.clone()),
+                PartSource::Bytes(bytes) => Segment::Bytes(bytes.clone()),
+                PartSource::Lazy(lazy) => Segment::Bytes(lazy.get().clone()),
+            });
+            segments.push_back(Segment::Bytes(crlf.clone()));
+        }
+        let terminator = format!("--{}--\r\n", boundary).into_bytes();
+        segments.push_back(Segment::Bytes(terminator.into()));
+        let len = parts
+            .iter()
+            .map(|part| part.encoded_len(boundary) as u64)
+            .sum::<u64>()
+            + boundary.len() as u64
+            + 6;
+        #[cfg(feature = "tracing")]
+        tracing::debug!(size = len, "built multipart reader");
+        MultiPartFormDataReader {
+            segments,
+            len,
+            read: 0,
+            progress: None,
+            cancellation: None,
+        }
+    }
+
+    /// Total body length in bytes
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Returns true when body is empty, never the case for a multipart body
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl Read for MultiPartFormDataReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if let Some(cancellation) = &self.cancellation {
+            cancellation.check()?;
+        }
+        while let Some(segment) = self.segments.front_mut() {
+            let read = match segment {
+                Segment::Bytes(bytes) => {
+                    let read = bytes.len().min(buf.len());
+                    bytes.copy_to_slice(&mut buf[..read]);
+                    read
+                }
+                Segment::Path(path) => {
+                    *segment = Segment::File(File::open(path)?);
+                    continue;
+                }
+                Segment::File(file) => file.read(buf)?,
+            };
+            if read > 0 || buf.is_empty() {
+                self.read += read as u64;
+                if let (Some(progress), true) = (&self.progress, read > 0) {
+                    progress(self.read, self.len);
+                }
+                return Ok(read);
+            }
+            self.segments.pop_front();
+        }
+        Ok(0)
+    }
+}
+
+/// Clear body and read multipart body of parts into it, calling progress after each chunk
+pub(crate) fn read_body_into(
+    boundary: &str,
+    parts: &[Part],
+    progress: Progress,
+    body: &mut Vec<u8>,
+) {
+    let mut reader = MultiPartFormDataReader::new(boundary, parts);
+    reader.progress = Some(progress);
+    body.clear();
+    body.reserve_exact(reader.len as usize);
+    let mut chunk = vec![0; 64 * 1024];
+    loop {
+        let read = reader.read(&mut chunk).unwrap();
+        if read == 0 {
+            break;
+        }
+        body.extend_from_slice(&chunk[..read]);
+    }
+}
+
+impl MultiPartFormDataBuilder {
+    /// Build multipart/form-data as a reader
+    ///
+    /// File contents are read in chunks while reading, so large files are never fully loaded
+    ///
+    /// Progress callback set with on_progress is called on each read, reads fail once the token
+    /// set with with_cancellation is cancelled
+    ///
+    /// Returns ((header_name, header_value), MultiPartFormDataReader)
+    pub fn build_reader(&self) -> ((String, String), MultiPartFormDataReader) {
+        let boundary = self.next_boundary();
+        let parts = self
+            .checked_parts(&boundary)
+            .unwrap_or_else(|error| panic!("{}", error));
+        self.record_boundary(&boundary);
+        let mut reader = MultiPartFormDataReader::new(&boundary, &parts);
+        reader.progress = self.progress.clone();
+        reader.cancellation = self.cancellation.clone();
+        (content_type_header(&boundary), reader)
+    }
+
+    /// Build multipart/form-data into writer
+    ///
+    /// File contents are copied in chunks, so large files are never fully loaded
+    ///
+    /// Returns (header_name, header_value) Content-Type header
+    pub fn build_to_writer(&self, writer: &mut impl Write) -> io::Result<(String, String)> {
+        let (header, mut reader) = self.build_reader();
+        io::copy(&mut reader, writer)?;
+        Ok(header)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part::write_body, MultiPartFormDataParser};
+
+    fn parts() -> Vec<Part> {
+        vec![
+            Part::file("tests/sample.png", "sample", "image/png", "sample.png"),
+            Part::text("name", "some_name"),
+        ]
+    }
+
+    #[test]
+    fn test_should_read_same_body_as_build() {
+        let mut reader = MultiPartFormDataReader::new("boundary", &parts());
+        let mut body = vec![];
+        reader.read_to_end(&mut body).unwrap();
+
+        assert_eq!(body, write_body("boundary", &parts(), None));
+        assert_eq!(reader.len(), body.len() as u64);
+    }
+
+    #[test]
+    fn test_should_read_body_without_parts() {
+        let mut reader = MultiPartFormDataReader::new("boundary", &[]);
+        let mut body = vec![];
+        reader.read_to_end(&mut body).unwrap();
+
+        assert_eq!(body, write_body("boundary", &[], None));
+    }
+
+    #[test]
+    fn test_should_build_to_writer() {
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder.with_part(parts().remove(0));
+        let mut body = vec![];
+        let (_, content_type) = multipart_form_data_builder
+            .build_to_writer(&mut body)
+            .unwrap();
+
+        let parsed = MultiPartFormDataParser::parse(&content_type, &body).unwrap();
+        parsed
+            .assert_part("sample")
+            .assert_data(std::fs::read("tests/sample.png").unwrap());
+    }
+
+    #[cfg(feature = "memmap2")]
+    #[test]
+    fn test_should_read_memory_mapped_file() {
+        let parts = vec![
+            Part::file("tests/sample.png", "sample", "image/png", "sample.png").memory_mapped(),
+        ];
+        let mut body = vec![];
+        MultiPartFormDataReader::new("boundary", &parts)
+            .read_to_end(&mut body)
+            .unwrap();
+
+        assert_eq!(body, write_body("boundary", &parts, None));
+    }
+
+    #[test]
+    fn test_should_report_progress_while_building_and_reading() {
+        let progress = Arc::new(std::sync::Mutex::new(vec![]));
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        let reported = progress.clone();
+        multipart_form_data_builder
+            .with_part(parts().remove(0))
+            .with_boundary("boundary")
+            .on_progress(move |written, total| reported.lock().unwrap().push((written, total)));
+
+        let (_, body) = multipart_form_data_builder.build();
+        let total = body.len() as u64;
+        assert_eq!(body, write_body("boundary", &parts()[..1], None));
+        let built = std::mem::take(&mut *progress.lock().unwrap());
+        assert!(built.len() > 1);
+        assert!(built.windows(2).all(|pair| pair[0].0 < pair[1].0));
+        assert_eq!(built.last(), Some(&(total, total)));
+
+        let (_, mut reader) = multipart_form_data_builder.build_reader();
+        io::copy(&mut reader, &mut io::sink()).unwrap();
+        assert_eq!(progress.lock().unwrap().last(), Some(&(total, total)));
+    }
+
+    #[test]
+    fn test_should_abort_reader_when_cancelled() {
+        let cancellation_token = CancellationToken::new();
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder
+            .with_part(parts().remove(0))
+            .with_cancellation(cancellation_token.clone());
+        let (_, mut reader) = multipart_form_data_builder.build_reader();
+
+        let mut chunk = [0; 1024];
+        assert!(reader.read(&mut chunk).unwrap() > 0);
+        cancellation_token.cancel();
+        let error = reader.read(&mut chunk).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::ConnectionAborted);
+        let error = multipart_form_data_builder
+            .build_to_writer(&mut vec![])
+            .unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::ConnectionAborted);
+    }
+}
@@ -0,0 +1,361 @@
+use std::{
+    io::{self, Read, Write},
+    net::{Shutdown, TcpStream, ToSocketAddrs},
+    thread,
+    time::Duration,
+};
+
+use crate::CancellationToken;
+
+/// Blocking sender of raw requests over TCP, e.g. for socket level upload tests
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::time::Duration;
+///
+/// use actix_multipart_test::{MultiPartFormDataBuilder, RawTcpSender};
+///
+/// let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+/// multipart_form_data_builder.with_text("name", "some_name");
+/// let request = multipart_form_data_builder.to_raw_http_request(
+///     "POST",
+///     "/upload",
+///     &[("Connection", "close")],
+/// );
+///
+/// let response = RawTcpSender::new()
+///     .with_slow_writes(16, Duration::from_millis(10))
+///     .send("127.0.0.1:8080", &request)
+///     .unwrap();
+/// ```
+pub struct RawTcpSender {
+    chunk_size: Option<usize>,
+    delay: Duration,
+    read_timeout: Duration,
+    close_write: bool,
+    cancellation: Option<CancellationToken>,
+}
+
+impl Default for RawTcpSender {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RawTcpSender {
+    /// Create new RawTcpSender writing the request at once, read timeout is 5 seconds
+    pub fn new() -> RawTcpSender {
+        RawTcpSender {
+            chunk_size: None,
+            delay: Duration::ZERO,
+            read_timeout: Duration::from_secs(5),
+            close_write: false,
+            cancellation: None,
+        }
+    }
+
+    /// Write request in chunks of chunk_size bytes with delay between writes
+    ///
+    /// Returns &mut RawTcpSender
+    pub fn with_slow_writes(&mut self, chunk_size: usize, delay: Duration) -> &mut RawTcpSender {
+        self.chunk_size = Some(chunk_size.max(1));
+        self.delay = delay;
+        self
+    }
+
+    /// Set how long to wait for response bytes, reading stops when nothing arrives in time
+    ///
+    /// Returns &mut RawTcpSender
+    pub fn with_read_timeout(&mut self, read_timeout: Duration) -> &mut RawTcpSender {
+        self.read_timeout = read_timeout;
+        self
+    }
+
+    /// Shut down write half after the request, e.g. to test half closed connections
+    ///
+    /// Returns &mut RawTcpSender
+    pub fn with_write_shutdown(&mut self) -> &mut RawTcpSender {
+        self.close_write = true;
+        self
+    }
+
+    /// Abort writing once cancellation_token is cancelled, checked before each chunk of slow
+    /// writes
+    ///
+    /// Connection is then shut down and send returns a ConnectionAborted error
+    ///
+    /// Returns &mut RawTcpSender
+    pub fn with_cancellation(
+        &mut self,
+        cancellation_token: CancellationToken,
+    ) -> &mut RawTcpSender {
+        self.cancellation = Some(cancellation_token);
+        self
+    }
+
+    /// Send request in two phases for "Expect: 100-continue"
+    ///
+    /// Head is written first, body is written after a "100" interim response or when nothing
+    /// arrives within read timeout, as RFC 7231 section 5.1.1 asks of clients, other responses
+    /// such as 417 or 413 are returned without sending the body
+    ///
+    /// addr is server address, e.g. "127.0.0.1:8080"
+    ///
+    /// head and body are from MultiPartFormDataBuilder::to_expect_continue_request
+    ///
+    /// Returns raw response bytes, with the interim response when the body was sent
+    pub fn send_expect_continue(
+        &self,
+        addr: impl ToSocketAddrs,
+        head: &[u8],
+        body: &[u8],
+    ) -> io::Result<Vec<u8>> {
+        let mut stream = TcpStream::connect(addr)?;
+        stream.set_read_timeout(Some(self.read_timeout))?;
+        self.write(&mut stream, head)?;
+        let mut response = vec![];
+        let mut buf = [0; 8192];
+        while !response.windows(4).any(|window| window == b"\r\n\r\n") {
+            match stream.read(&mut buf) {
+                Err(error) if is_timeout(&error) && response.is_empty() => {
+                    self.write(&mut stream, body)?;
+                    return self.finish(&mut stream, response);
+                }
+                Err(error) if is_timeout(&error) => return Ok(response),
+                Err(error) => return Err(error),
+                Ok(0) => return Ok(response),
+                Ok(read) => response.extend_from_slice(&buf[..read]),
+            }
+        }
+        if !response.starts_with(b"HTTP/1.1 100") && !response.starts_with(b"HTTP/1.0 100") {
+            return self.finish(&mut stream, response);
+        }
+        self.write(&mut stream, body)?;
+        self.finish(&mut stream, response)
+    }
+
+    /// Connect, write request and read response until the server closes or read timeout
+    ///
+    /// addr is server address, e.g. "127.0.0.1:8080"
+    ///
+    /// request is raw request, e.g. from MultiPartFormDataBuilder::to_raw_http_request
+    ///
+    /// Returns raw response bytes, write errors such as connection resets are returned as is
+    pub fn send(&self, addr: impl ToSocketAddrs, request: &[u8]) -> io::Result<Vec<u8>> {
+        let mut stream = TcpStream::connect(addr)?;
+        stream.set_read_timeout(Some(self.read_timeout))?;
+        self.write(&mut stream, request)?;
+        self.finish(&mut stream, vec![])
+    }
+
+    fn write(&self, stream: &mut TcpStream, data: &[u8]) -> io::Result<()> {
+        match self.chunk_size {
+            Some(chunk_size) => {
+                for chunk in data.chunks(chunk_size) {
+                    if let Some(cancellation) = &self.cancellation {
+                        cancellation.check().inspect_err(|_| {
+                            let _ = stream.shutdown(Shutdown::Both);
+                        })?;
+                    }
+                    stream.write_all(chunk)?;
+                    stream.flush()?;
+                    thread::sleep(self.delay);
+                }
+                Ok(())
+            }
+            None => stream.write_all(data),
+        }
+    }
+
+    fn finish(&self, stream: &mut TcpStream, mut response: Vec<u8>) -> io::Result<Vec<u8>> {
+        if self.close_write {
+            stream.shutdown(Shutdown::Write)?;
+        }
+        self.read_to_end(stream, &mut response)?;
+        Ok(response)
+    }
+
+    fn read_to_end(&self, stream: &mut TcpStream, response: &mut Vec<u8>) -> io::Result<()> {
+        let mut buf = [0; 8192];
+        loop {
+            match self.read_some(stream, &mut buf)? {
+                0 => return Ok(()),
+                read => response.extend_from_slice(&buf[..read]),
+            }
+        }
+    }
+
+    /// Read into buf, read timeout is reported as end of response
+    fn read_some(&self, stream: &mut TcpStream, buf: &mut [u8]) -> io::Result<usize> {
+        match stream.read(buf) {
+            Err(error) if is_timeout(&error) => Ok(0),
+            result => result,
+        }
+    }
+}
+
+fn is_timeout(error: &io::Error) -> bool {
+    matches!(
+        error.kind(),
+        io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::TcpListener;
+
+    use super::*;
+
+    fn read_head(stream: &mut TcpStream) -> Vec<u8> {
+        let mut head = vec![];
+        let mut buf = [0; 1];
+        while !head.ends_with(b"\r\n\r\n") {
+            stream.read_exact(&mut buf).unwrap();
+            head.push(buf[0]);
+        }
+        head
+    }
+
+    #[test]
+    fn test_should_send_body_after_continue() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let head = read_head(&mut stream);
+            stream.write_all(b"HTTP/1.1 100 Continue\r\n\r\n").unwrap();
+            let mut body = [0; 4];
+            stream.read_exact(&mut body).unwrap();
+            stream.write_all(b"HTTP/1.1 200 OK\r\n\r\n").unwrap();
+            (head, body)
+        });
+
+        let response = RawTcpSender::new()
+            .send_expect_continue(
+                addr,
+                b"POST / HTTP/1.1\r\nExpect: 100-continue\r\n\r\n",
+                b"body",
+            )
+            .unwrap();
+
+        let (head, body) = server.join().unwrap();
+        assert!(head.ends_with(b"Expect: 100-continue\r\n\r\n"));
+        assert_eq!(&body, b"body");
+        assert_eq!(
+            response,
+            b"HTTP/1.1 100 Continue\r\n\r\nHTTP/1.1 200 OK\r\n\r\n"
+        );
+    }
+
+    #[test]
+    fn test_should_send_body_when_server_ignores_expect() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            read_head(&mut stream);
+            let mut body = [0; 4];
+            stream.read_exact(&mut body).unwrap();
+            stream.write_all(b"HTTP/1.1 200 OK\r\n\r\n").unwrap();
+            body
+        });
+
+        let response = RawTcpSender::new()
+            .with_read_timeout(Duration::from_millis(200))
+            .send_expect_continue(
+                addr,
+                b"POST / HTTP/1.1\r\nExpect: 100-continue\r\n\r\n",
+                b"body",
+            )
+            .unwrap();
+
+        assert_eq!(&server.join().unwrap(), b"body");
+        assert_eq!(response, b"HTTP/1.1 200 OK\r\n\r\n");
+    }
+
+    #[test]
+    fn test_should_not_send_body_after_rejection() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            read_head(&mut stream);
+            stream
+                .write_all(b"HTTP/1.1 413 Payload Too Large\r\n\r\n")
+                .unwrap();
+            let mut rest = vec![];
+            stream.read_to_end(&mut rest).unwrap();
+            rest
+        });
+
+        let response = RawTcpSender::new()
+            .with_read_timeout(Duration::from_millis(200))
+            .with_write_shutdown()
+            .send_expect_continue(addr, b"POST / HTTP/1.1\r\n\r\n", b"body")
+            .unwrap();
+
+        assert_eq!(response, b"HTTP/1.1 413 Payload Too Large\r\n\r\n");
+        assert!(server.join().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_should_send_slow_writes_and_read_response() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = vec![];
+            let mut reads = 0;
+            let mut buf = [0; 64];
+            while !request.ends_with(b"\r\n\r\n") {
+                let read = stream.read(&mut buf).unwrap();
+                request.extend_from_slice(&buf[..read]);
+                reads += 1;
+            }
+            stream
+                .write_all(b"HTTP/1.1 204 No Content\r\n\r\n")
+                .unwrap();
+            (request, reads)
+        });
+
+        let response = RawTcpSender::new()
+            .with_slow_writes(4, Duration::from_millis(5))
+            .send(addr, b"GET / HTTP/1.1\r\n\r\n")
+            .unwrap();
+
+        let (request, reads) = server.join().unwrap();
+        assert_eq!(request, b"GET / HTTP/1.1\r\n\r\n");
+        assert!(reads > 1);
+        assert_eq!(response, b"HTTP/1.1 204 No Content\r\n\r\n");
+    }
+
+    #[test]
+    fn test_should_abort_slow_writes_when_cancelled() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut received = vec![];
+            let _ = stream.read_to_end(&mut received);
+            received
+        });
+        let cancellation_token = CancellationToken::new();
+        let cancel = cancellation_token.clone();
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            cancel.cancel();
+        });
+
+        let error = RawTcpSender::new()
+            .with_slow_writes(1, Duration::from_millis(10))
+            .with_cancellation(cancellation_token)
+            .send(addr, &[b'a'; 1000])
+            .unwrap_err();
+
+        assert_eq!(error.kind(), io::ErrorKind::ConnectionAborted);
+        let received = server.join().unwrap();
+        assert!(!received.is_empty() && received.len() < 1000);
+    }
+}
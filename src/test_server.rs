@@ -0,0 +1,153 @@
+use actix_test::TestServer;
+use awc::SendClientRequest;
+
+use crate::MultiPartFormDataBuilder;
+
+/// Send built multipart/form-data to a test server over real HTTP
+///
+/// srv is server started with actix_test::start
+///
+/// path is request path, e.g. "/upload"
+///
+/// Returns request future, awaiting it gives the response
+///
+/// # Examples
+///
+/// ```no_run
+/// use actix_multipart_test::{post_multipart, MultiPartFormDataBuilder};
+/// use actix_web::{web, App, HttpResponse};
+///
+/// #[actix_web::main]
+/// async fn main() {
+///     let srv = actix_test::start(|| App::new().route("/upload", web::post().to(HttpResponse::Ok)));
+///     let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+///     multipart_form_data_builder.with_text("name", "some_name");
+///
+///     let resp = post_multipart(&srv, "/upload", &multipart_form_data_builder)
+///         .await
+///         .unwrap();
+///
+///     assert!(resp.status().is_success());
+/// }
+/// ```
+pub fn post_multipart(
+    srv: &TestServer,
+    path: &str,
+    multipart_form_data_builder: &MultiPartFormDataBuilder,
+) -> SendClientRequest {
+    let form_data = multipart_form_data_builder.build_form_data();
+    let mut request = srv.post(path);
+    for header in form_data.headers {
+        request = request.insert_header(header);
+    }
+    request.send_body(form_data.body)
+}
+
+/// Send multipart/form-data to a test server over real HTTP as a chunked stream
+///
+/// srv is server started with actix_test::start
+///
+/// path is request path, e.g. "/upload"
+///
+/// chunk_size is maximum number of body bytes per chunk
+///
+/// File contents are read while sending, the upload is aborted once the token set with
+/// with_cancellation is cancelled, e.g. to test server side cleanup of partial uploads
+///
+/// Returns request future, awaiting it gives the response
+pub fn post_multipart_stream(
+    srv: &TestServer,
+    path: &str,
+    multipart_form_data_builder: &MultiPartFormDataBuilder,
+    chunk_size: usize,
+) -> SendClientRequest {
+    let (content_type, stream) = multipart_form_data_builder.build_stream(chunk_size);
+    let mut request = srv.post(path).insert_header(content_type);
+    for header in multipart_form_data_builder.request_headers.iter().cloned() {
+        request = request.insert_header(header);
+    }
+    request.send_stream(stream)
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{web, App, HttpRequest, HttpResponse};
+
+    use super::*;
+    use crate::CancellationToken;
+
+    async fn content_length(req: HttpRequest, body: web::Bytes) -> HttpResponse {
+        let header = req
+            .headers()
+            .get("Content-Length")
+            .unwrap()
+            .to_str()
+            .unwrap();
+        assert_eq!(header, body.len().to_string());
+        HttpResponse::Ok().body(body.len().to_string())
+    }
+
+    #[actix_web::test]
+    async fn test_should_post_multipart_to_test_server() {
+        let srv = actix_test::start(|| App::new().route("/upload", web::post().to(content_length)));
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder.with_text("name", "some_name");
+
+        let mut resp = post_multipart(&srv, "/upload", &multipart_form_data_builder)
+            .await
+            .unwrap();
+
+        assert!(resp.status().is_success());
+        let body = resp.body().await.unwrap();
+        assert_eq!(
+            body,
+            multipart_form_data_builder.build().1.len().to_string()
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_should_post_multipart_stream_to_test_server() {
+        let srv = actix_test::start(|| {
+            App::new().route(
+                "/upload",
+                web::post().to(|body: web::Bytes| async move {
+                    HttpResponse::Ok().body(body.len().to_string())
+                }),
+            )
+        });
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder.with_file(
+            "tests/sample.png",
+            "sample",
+            "image/png",
+            "sample.png",
+        );
+
+        let mut resp = post_multipart_stream(&srv, "/upload", &multipart_form_data_builder, 1024)
+            .await
+            .unwrap();
+
+        assert!(resp.status().is_success());
+        let body = resp.body().limit(1024 * 1024).await.unwrap();
+        assert_eq!(
+            body,
+            multipart_form_data_builder.build().1.len().to_string()
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_should_abort_cancelled_multipart_stream() {
+        let srv =
+            actix_test::start(|| App::new().route("/upload", web::post().to(HttpResponse::Ok)));
+        let cancellation_token = CancellationToken::new();
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder
+            .with_text("name", "some_name")
+            .with_cancellation(cancellation_token.clone());
+        cancellation_token.cancel();
+
+        let resp = post_multipart_stream(&srv, "/upload", &multipart_form_data_builder, 1024).await;
+
+        assert!(resp.is_err());
+    }
+}
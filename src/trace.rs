@@ -0,0 +1,99 @@
+use tracing::span::EnteredSpan;
+
+use crate::part::{Part, PartSource};
+
+/// Enter build span and emit an event for each part
+pub(crate) fn build_span(boundary: &str, parts: &[Part]) -> EnteredSpan {
+    let span = tracing::debug_span!("build", boundary, parts = parts.len()).entered();
+    for part in parts.iter() {
+        let name = String::from_utf8_lossy(&part.name);
+        match &part.source {
+            PartSource::File(path) => tracing::debug!(
+                name = %name,
+                size = part.content_len(),
+                source = "file",
+                path = %path.display(),
+                "part"
+            ),
+            PartSource::Bytes(_) => {
+                tracing::debug!(name = %name, size = part.content_len(), source = "bytes", "part")
+            }
+            PartSource::Lazy(_) => {
+                tracing::debug!(name = %name, size = part.content_len(), source = "lazy", "part")
+            }
+        }
+    }
+    span
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fmt::Debug,
+        sync::{Arc, Mutex},
+    };
+
+    use tracing::{
+        field::{Field, Visit},
+        span, Event, Metadata, Subscriber,
+    };
+
+    use crate::MultiPartFormDataBuilder;
+
+    /// Subscriber recording event fields as "name=value" lines
+    #[derive(Clone, Default)]
+    struct Recorder(Arc<Mutex<Vec<String>>>);
+
+    struct Fields(Vec<String>);
+
+    impl Visit for Fields {
+        fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+            self.0.push(format!("{}={:?}", field.name(), value));
+        }
+    }
+
+    impl Subscriber for Recorder {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, _: &span::Attributes<'_>) -> span::Id {
+            span::Id::from_u64(1)
+        }
+
+        fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+
+        fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            let mut fields = Fields(vec![]);
+            event.record(&mut fields);
+            self.0.lock().unwrap().push(fields.0.join(" "));
+        }
+
+        fn enter(&self, _: &span::Id) {}
+
+        fn exit(&self, _: &span::Id) {}
+    }
+
+    #[test]
+    fn test_should_trace_parts_and_total_size() {
+        let recorder = Recorder::default();
+        let mut multipart_form_data_builder = MultiPartFormDataBuilder::new();
+        multipart_form_data_builder
+            .with_text("name", "some_name")
+            .with_file("tests/sample.png", "sample", "image/png", "sample.png");
+        let (_, body) = tracing::subscriber::with_default(recorder.clone(), || {
+            multipart_form_data_builder.build()
+        });
+
+        let events = recorder.0.lock().unwrap();
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[0], "message=part name=name size=9 source=\"bytes\"");
+        assert!(events[1].contains("name=sample") && events[1].contains("source=\"file\""));
+        assert_eq!(
+            events[2],
+            format!("message=built multipart body size={}", body.len())
+        );
+    }
+}